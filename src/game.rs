@@ -4,25 +4,63 @@ mod board_map;
 mod builder;
 mod castling_rights;
 mod castlinginfo;
+pub mod checkstatus;
+pub mod clock;
+mod deadposition;
 mod display;
 mod enpassant;
+pub mod epd;
+mod evaluation;
+pub mod evasion;
+mod fen;
+pub mod filestatus;
 pub mod game_error;
 pub mod gamestate;
 mod king;
+mod movebytes;
 mod movement;
+pub mod moverecord;
+pub mod pawnstructure;
+mod perft;
+mod piecedrop;
 mod play;
-mod side;
+mod pocket;
+pub mod rawturn;
+mod search;
+pub mod side;
 mod simulation;
 mod state;
+mod transform;
+pub mod transposition;
+pub mod undoinfo;
+pub mod variant;
 
 use super::DisplayOption;
 use availableturn::AvailableTurn;
 use board::Board;
-use chess_notation_parser::Piece;
+pub use board_map::{
+    coords_to_square, index_to_square, square_to_coords, squares_between,
+};
+use checkstatus::CheckStatus;
+use chess_notation_parser::{CastlingType, Piece, Square};
+use clock::Clock;
+use epd::EpdOps;
+use evasion::CheckEvasionKind;
+pub use fen::validate as validate_fen;
+use filestatus::FileStatus;
 use game_error::GameError;
 use gamestate::Gamestate;
+use movement::PieceMove;
+use moverecord::MoveRecord;
+use pawnstructure::PawnStructure;
+use rawturn::RawTurn;
 use side::Side;
 use state::State;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+use undoinfo::UndoInfo;
+use variant::GameVariant;
 
 /// Default chessboard setup
 pub const NORMAL_SETUP: &str = "
@@ -32,18 +70,65 @@ pub const NORMAL_SETUP: &str = "
     a8,b,R b8,b,N c8,b,B d8,b,Q e8,b,K f8,b,B g8,b,N h8,b,R
 ";
 
+/// Turn taken back by `Game::takeback`
+#[derive(Clone)]
+struct RedoTurn {
+    /// Hash of the position the turn is played from
+    key: u64,
+
+    /// Turn in chess notation format
+    turn: String,
+
+    /// Time spent on the turn, if it was timed
+    duration: Option<Duration>,
+
+    /// Clock right after the turn was played
+    clock: Option<Clock>,
+}
+
 /// Chess game
+#[derive(Clone)]
 pub struct Game {
     /// Turn history in vector of `State`s.
     history: Vec<State>,
 
+    /// Available turns of every position from the `history`
+    ///
+    /// Undoing the turn restores them instead of recalculating them
+    raw_turns_history: Vec<OnceLock<Vec<RawTurn>>>,
+
     /// Board contains all the info about current game
     board: Board,
 
     /// List of available turns
     ///
-    /// It's calculated after each turn is played
-    available_turns: Vec<AvailableTurn>,
+    /// It's calculated after each turn is played, unless the eager move
+    /// generation is disabled. Then it's calculated once it's requested.
+    raw_turns: OnceLock<Vec<RawTurn>>,
+
+    /// Whether available turns are calculated right after each turn
+    eager_move_generation: bool,
+
+    /// Available turns in the string format
+    ///
+    /// Strings are created out of `raw_turns` only once they are requested
+    available_turns: OnceLock<Vec<AvailableTurn>>,
+
+    /// Optional chess clock, charged only by timed turns
+    clock: Option<Clock>,
+
+    /// Clock of every position from the `history`
+    clock_history: Vec<Option<Clock>>,
+
+    /// FEN record of the position the game started from
+    setup_fen: String,
+
+    /// Turns taken back by `takeback`, the most recent one last
+    ///
+    /// Every turn is stored along with the hash of the position it's played
+    /// from, so turns no longer leading from the current position are never
+    /// redone.
+    redo_turns: Vec<RedoTurn>,
 }
 
 impl Game {
@@ -52,6 +137,64 @@ impl Game {
         builder::create(setup)
     }
 
+    /// Create `Game` instance played by the rules of the `variant`
+    pub fn with_variant(
+        setup: &'static str,
+        variant: GameVariant,
+    ) -> Result<Game, &'static str> {
+        let mut game = builder::create(setup)?;
+        // Pockets start empty, so available turns stay the same
+        game.board.variant = variant;
+        Ok(game)
+    }
+
+    /// Create `Game` instance out of a FEN record
+    pub fn from_fen(fen: &str) -> Result<Game, GameError> {
+        let board = fen::parse(fen).map_err(GameError::ParsingFenFailed)?;
+        let mut game = builder::create_from_board(board);
+
+        // Keep the full move number which the board doesn't track
+        game.setup_fen = fen.split_whitespace().collect::<Vec<_>>().join(" ");
+        Ok(game)
+    }
+
+    /// Create `Game` instance out of an EPD record and return its operations
+    pub fn from_epd(epd: &str) -> Result<(Game, EpdOps), GameError> {
+        let (board, ops) =
+            epd::parse(epd).map_err(GameError::ParsingFenFailed)?;
+        Ok((builder::create_from_board(board), ops))
+    }
+
+    /// Create `Game` instance out of the encoded squares
+    pub fn from_board_bytes(
+        bytes: [u8; 64],
+        active_player: Side,
+    ) -> Result<Game, GameError> {
+        builder::create_from_bytes(bytes, active_player)
+            .map_err(GameError::InvalidBoardBytes)
+    }
+
+    /// Create `Game` instance with the default setup and replay turns
+    /// encoded with `to_move_bytes`
+    pub fn from_move_bytes(bytes: &[u8]) -> Result<Game, GameError> {
+        let mut game = Game::new(NORMAL_SETUP).expect("Default setup is valid");
+        movebytes::decode(&mut game, bytes)?;
+        Ok(game)
+    }
+
+    /// Encode played turns into 2 bytes each
+    ///
+    /// Only games started from the default setup in the standard variant can
+    /// be encoded.
+    pub fn to_move_bytes(&self) -> Result<Vec<u8>, GameError> {
+        movebytes::encode(self)
+    }
+
+    /// Get FEN record of the position the game started from
+    pub fn setup_fen(&self) -> &str {
+        &self.setup_fen
+    }
+
     /// Prepare a string that displays the board
     pub fn display(&self, display_opt: DisplayOption) -> String {
         display::display_game(self, display_opt)
@@ -62,28 +205,248 @@ impl Game {
         self.board.gamestate.clone()
     }
 
+    /// Get check status of the king of the player on turn
+    pub fn check_status(&self) -> CheckStatus {
+        let mut board = self.board.clone();
+        let side = board.active_player;
+        match king::get_state(&mut board, side) {
+            king::KingState::Safe => CheckStatus::Safe,
+            king::KingState::Check => CheckStatus::Check,
+            // King cannot escape, but other pieces might stop the check
+            king::KingState::SoftCheckmate => {
+                match simulation::has_legal_turn(&mut board) {
+                    true => CheckStatus::Check,
+                    false => CheckStatus::Checkmate,
+                }
+            }
+        }
+    }
+
+    /// Get game result as a PGN result token
+    pub fn result_token(&self) -> &'static str {
+        self.board.gamestate.result_token()
+    }
+
     /// Play turn and update the board status.
-    /// Returns old state of the board.
+    /// Leading move number, e.g. `12.` in `12. Nf3`, is ignored.
     pub fn play_turn(&mut self, turn: &str) -> Result<Gamestate, GameError> {
+        self.play_turn_strict(play::strip_move_number(turn))
+    }
+
+    /// Play whitespace separated turns until the first one which fails
+    ///
+    /// Move numbers standing on their own, e.g. `1.` in `1. e4 e5`, are
+    /// skipped. Turns played before the failing one are kept.
+    pub fn play_sequence(
+        &mut self,
+        turns: &str,
+    ) -> Result<Gamestate, (String, GameError)> {
+        for turn in turns.split_whitespace() {
+            if play::strip_move_number(turn).is_empty() {
+                continue;
+            }
+
+            self.play_turn(turn).map_err(|e| (turn.to_string(), e))?;
+        }
+
+        Ok(self.gamestate())
+    }
+
+    /// Play turn written in the long algebraic format used by UCI, e.g.
+    /// `e7e8q`
+    pub fn play_uci(&mut self, uci: &str) -> Result<Gamestate, GameError> {
+        if self.board.gamestate != Gamestate::Ongoing {
+            return Err(GameError::GameOver(self.board.gamestate.clone()));
+        }
+
+        let turn = self
+            .raw_turns()
+            .iter()
+            .find(|raw| raw.to_uci() == uci)
+            .map(RawTurn::get_turn)
+            .ok_or_else(|| GameError::IllegalUciMove(uci.to_string()))?;
+
+        self.play_turn_strict(&turn)
+    }
+
+    /// Play turns picked by `select` out of the available turns until the
+    /// game is over or `max_plies` turns are played
+    pub fn play_out<F>(&mut self, max_plies: usize, mut select: F) -> Gamestate
+    where
+        F: FnMut(&[AvailableTurn]) -> usize,
+    {
+        for _ in 0..max_plies {
+            if self.board.gamestate != Gamestate::Ongoing {
+                break;
+            }
+
+            let turns = self.available_turns();
+            let turn = turns[select(turns)].get_turn().to_string();
+            self.play_turn_strict(&turn)
+                .expect("Available turn must be playable");
+        }
+
+        self.gamestate()
+    }
+
+    /// Play turn written exactly in chess notation format
+    pub fn play_turn_strict(
+        &mut self,
+        turn: &str,
+    ) -> Result<Gamestate, GameError> {
+        let gamestate = self.play_turn_keep_redo(turn)?;
+
+        // New turn leaves nothing to redo
+        self.redo_turns.clear();
+        Ok(gamestate)
+    }
+
+    /// Play turn without discarding the turns taken back by `takeback`
+    ///
+    /// Searches play and undo their turns on the game itself, so they leave
+    /// the taken back turns intact.
+    fn play_turn_keep_redo(
+        &mut self,
+        turn: &str,
+    ) -> Result<Gamestate, GameError> {
         self.history.push(self.board.next_turn(turn)?);
 
+        // Turns of the previous position are needed again on undo
+        let prev_raw_turns = std::mem::take(&mut self.raw_turns);
+        self.raw_turns_history.push(prev_raw_turns);
+        self.available_turns = OnceLock::new();
+        self.clock_history.push(self.clock);
+
         if self.board.gamestate != Gamestate::Ongoing {
+            self.set_raw_turns(vec![]);
             return Ok(self.board.gamestate.clone());
         }
 
         // For an ongoing game update available turns
-        self.available_turns = simulation::get_available_turns(&mut self.board);
+        if self.eager_move_generation {
+            let raw_turns = simulation::get_available_turns(&mut self.board);
+            self.set_raw_turns(raw_turns);
+        }
 
         // Some draw conditions are set in `board.next_turn` function
         self.check_few_draw_conditions();
 
         if self.board.gamestate != Gamestate::Ongoing {
-            self.available_turns = vec![];
+            self.set_raw_turns(vec![]);
         }
 
         Ok(self.board.gamestate.clone())
     }
 
+    /// Pass the turn to the opponent without moving any piece
+    ///
+    /// It's recorded in the history as the null turn `--` and it's undone
+    /// like any other turn.
+    pub fn toggle_side_to_move(&mut self) -> Result<Gamestate, GameError> {
+        self.play_turn_strict(play::NULL_TURN)
+    }
+
+    /// Play turn and remember how long it took to make it
+    ///
+    /// In case the clock is set, the player on turn is charged for the
+    /// `elapsed` time and loses the ongoing game once the time runs out.
+    /// The turn is not played when the time has run out.
+    pub fn play_turn_timed(
+        &mut self,
+        turn: &str,
+        elapsed: Duration,
+    ) -> Result<Gamestate, GameError> {
+        if self.board.gamestate != Gamestate::Ongoing {
+            return self.play_turn(turn);
+        }
+
+        let side = self.board.active_player;
+        let mut clock = self.clock;
+        let flag_fall = match clock.as_mut() {
+            Some(clock) => !clock.spend(side, elapsed),
+            None => false,
+        };
+
+        if flag_fall {
+            self.clock = clock;
+
+            // Opponent without mating material cannot win on time
+            self.board.gamestate =
+                match self.has_mating_material(side.opponent()) {
+                    true => Gamestate::Victory(side.opponent().to_string()),
+                    _ => Gamestate::DrawInsufficientMatingMaterial,
+                };
+            self.set_raw_turns(vec![]);
+
+            return Ok(self.board.gamestate.clone());
+        }
+
+        let gamestate = self.play_turn(turn)?;
+
+        let state = self.history.last_mut().expect("Turn was just played");
+        state.duration = Some(elapsed);
+        self.clock = clock;
+
+        Ok(gamestate)
+    }
+
+    /// Set number of half moves which ends the game by the fifty move rule
+    pub fn set_fifty_move_limit(&mut self, limit: u8) {
+        self.board.fifty_move_limit = limit;
+    }
+
+    /// Enable or disable ending the game by the fifty move rule, threefold
+    /// repetition and insufficient mating material
+    pub fn detect_draws(&mut self, detect: bool) {
+        self.board.detect_draws = detect;
+    }
+
+    /// Enable or disable calculating available turns right after each turn
+    ///
+    /// Once disabled, available turns are calculated only when they are
+    /// requested for the first time in the current position.
+    pub fn eager_move_generation(&mut self, eager: bool) {
+        self.eager_move_generation = eager;
+        if eager {
+            self.raw_turns();
+        }
+    }
+
+    /// Get number of half moves since the last capture or pawn move
+    pub fn halfmoves_since_irreversible(&self) -> u8 {
+        self.board.fifty_move_rule
+    }
+
+    /// Get how many times the current position occurred since the last
+    /// irreversible turn
+    pub fn repetition_count(&self) -> u8 {
+        self.board.repetition_count()
+    }
+
+    /// Get hash of the current position
+    pub fn position_key(&self) -> u64 {
+        self.board.position_key()
+    }
+
+    /// Check whether the fifty move rule ends the game within `within` half
+    /// moves
+    pub fn is_near_fifty_move_draw(&self, within: u8) -> bool {
+        self.board
+            .fifty_move_limit
+            .saturating_sub(self.board.fifty_move_rule)
+            <= within
+    }
+
+    /// Set the chess clock which is charged by timed turns
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = Some(clock);
+    }
+
+    /// Get remaining time of the `side` in case the clock is set
+    pub fn time_remaining(&self, side: Side) -> Option<Duration> {
+        self.clock.map(|clock| clock.time_remaining(side))
+    }
+
     /// Undo turn and restore board state
     pub fn undo_turn(&mut self) -> Result<(), GameError> {
         let prev_state = match self.history.pop() {
@@ -94,36 +457,782 @@ impl Game {
         self.board.active_player.switch_side();
         // Switch player before calculating hash! Anything else is a headache!
         self.board.hash_state_pop();
+        if prev_state.state_hashes_cleared {
+            self.board.restore_state_hashes();
+        }
+        self.board.undo_pockets(&prev_state);
         self.board.undo(prev_state);
 
-        self.available_turns = simulation::get_available_turns(&mut self.board);
+        // Turns of the position might not have been calculated yet
+        self.raw_turns = self
+            .raw_turns_history
+            .pop()
+            .expect("Turns are stored for every turn in history");
+        self.available_turns = OnceLock::new();
+
+        self.clock = self
+            .clock_history
+            .pop()
+            .expect("Clock is stored for every turn in history");
         Ok(())
     }
 
+    /// Undo turn and get the record of the undone turn
+    pub fn undo_turn_info(&mut self) -> Result<MoveRecord, GameError> {
+        let record = self
+            .history
+            .last()
+            .map(MoveRecord::from)
+            .ok_or(GameError::UndoNotAvailable)?;
+
+        self.takeback()?;
+        Ok(record)
+    }
+
+    /// Undo turn and get the squares and pieces it changed
+    pub fn undo_turn_details(&mut self) -> Result<UndoInfo, GameError> {
+        let info = self
+            .history
+            .last()
+            .map(|state| {
+                UndoInfo::new(state, self.board.active_player.opponent())
+            })
+            .ok_or(GameError::UndoNotAvailable)?;
+
+        self.takeback()?;
+        Ok(info)
+    }
+
+    /// Undo turn and remember it, so it can be redone
+    pub fn takeback(&mut self) -> Result<(), GameError> {
+        let state = self.history.last().ok_or(GameError::UndoNotAvailable)?;
+        let turn = state.get_turn().to_string();
+        let duration = state.duration;
+        let clock = self.clock;
+
+        self.undo_turn()?;
+        self.redo_turns.push(RedoTurn {
+            key: self.position_key(),
+            turn,
+            duration,
+            clock,
+        });
+        Ok(())
+    }
+
+    /// Play again the most recently taken back turn
+    ///
+    /// Time spent on the turn and the clock are restored as well.
+    pub fn redo_turn(&mut self) -> Result<Gamestate, GameError> {
+        let redo = match self.redo_turns.pop() {
+            Some(redo) if redo.key == self.position_key() => redo,
+            // Another turn was played since, so nothing can be redone
+            Some(_) => {
+                self.redo_turns.clear();
+                return Err(GameError::RedoNotAvailable);
+            }
+            None => return Err(GameError::RedoNotAvailable),
+        };
+
+        // Turns taken back before this one can still be redone
+        let gamestate = self.play_turn_keep_redo(&redo.turn)?;
+
+        let state = self.history.last_mut().expect("Turn was just played");
+        state.duration = redo.duration;
+        self.clock = redo.clock;
+
+        Ok(gamestate)
+    }
+
+    /// Take back or redo turns until exactly `ply` turns are played
+    ///
+    /// Available turns are calculated only in the target position.
+    pub fn goto_ply(&mut self, ply: usize) -> Result<(), GameError> {
+        let eager = self.eager_move_generation;
+        self.eager_move_generation = false;
+
+        let mut result = Ok(());
+        while result.is_ok() && self.history.len() > ply {
+            result = self.takeback();
+        }
+        while result.is_ok() && self.history.len() < ply {
+            result = self.redo_turn().map(|_| ());
+        }
+
+        self.eager_move_generation(eager);
+        result
+    }
+
+    /// Create a new game with swapped colors of all pieces and the player on
+    /// turn
+    pub fn mirror_colors(&self) -> Game {
+        builder::create_from_board(transform::mirror_colors(&self.board))
+    }
+
+    /// Create a new game with the board rotated by 180 degrees
+    pub fn rotate_180(&self) -> Result<Game, &'static str> {
+        transform::rotate_180(&self.board).map(builder::create_from_board)
+    }
+
+    /// Get FEN record of the current position
+    pub fn fen(&self) -> String {
+        let (setup_fullmove, black_started) = self.setup_fullmove();
+        let plies = self.history.len() + black_started as usize;
+        fen::export(&self.board, setup_fullmove + plies / 2)
+    }
+
+    /// Get full move number of the setup and whether Black was on turn
+    fn setup_fullmove(&self) -> (usize, bool) {
+        let mut setup = self.setup_fen.split_whitespace();
+        let black_started = setup.nth(1) == Some("b");
+        let fullmove = setup.last().and_then(|n| n.parse().ok()).unwrap_or(1);
+        (fullmove, black_started)
+    }
+
+    /// Get every available turn along with the FEN record of the position
+    /// it leads to
+    pub fn successors(&self) -> Vec<(String, String)> {
+        let mut game = self.clone();
+        // Turns of the successors are not needed
+        game.eager_move_generation = false;
+
+        self.available_turns()
+            .iter()
+            .map(|turn| {
+                game.play_turn_strict(turn.get_turn())
+                    .expect("Available turn must be playable");
+                let fen = game.fen();
+                game.undo_turn().expect("Turn was just played");
+                (turn.get_turn().to_string(), fen)
+            })
+            .collect()
+    }
+
+    /// Get piece placement field of the FEN record
+    pub fn fen_placement(&self) -> String {
+        fen::placement(&self.board.map)
+    }
+
+    /// Check whether both games have the same position on the board
+    pub fn same_position(&self, other: &Game) -> bool {
+        self.board.same_position(&other.board)
+    }
+
+    /// Check whether the `side` is able to castle with the next turn
+    pub fn can_castle(&self, side: Side, castling_type: CastlingType) -> bool {
+        self.board.gamestate == Gamestate::Ongoing
+            && self.board.active_player == side
+            && self.board.castling_rights.contains(&(side, castling_type))
+            && play::verify_castling(&self.board, castling_type).is_ok()
+    }
+
+    /// Get home square of the rook which castles, if the right still exists
+    pub fn castling_rook_square(
+        &self,
+        side: Side,
+        castling_type: CastlingType,
+    ) -> Option<Square> {
+        match self.board.castling_rights.contains(&(side, castling_type)) {
+            true => Some(castlinginfo::get_path_rook(side, castling_type).src),
+            _ => None,
+        }
+    }
+
+    /// Get squares with pieces defended by the `side` piece on `square`
+    pub fn defended_squares(&self, square: Square, side: Side) -> Vec<Square> {
+        let piece = match self.board.map.get(&square) {
+            Some((piece, piece_side)) if piece_side == side => piece,
+            _ => return vec![],
+        };
+
+        let piece_move = match piece {
+            Piece::Pawn => PieceMove::PawnCapture,
+            piece => PieceMove::from(piece),
+        };
+
+        movement::defended_squares(&self.board.map, square, side, piece_move)
+    }
+
+    /// Get squares of the `side` pieces which attack the `square`
+    pub fn attackers_of(&self, square: Square, side: Side) -> Vec<Square> {
+        movement::attackers(&self.board.map, square, side)
+    }
+
+    /// Count white and black pieces attacking every square
+    ///
+    /// Squares are ordered as in `board_bytes`, rows go from the rank 8 to
+    /// the rank 1.
+    pub fn control_map(&self) -> [[(u8, u8); 8]; 8] {
+        let mut control = [[(0, 0); 8]; 8];
+
+        for idx in 0..64u8 {
+            let square = Square::from(idx);
+            let count = |side| self.attackers_of(square, side).len() as u8;
+            control[idx as usize / 8][idx as usize % 8] =
+                (count(Side::White), count(Side::Black));
+        }
+
+        control
+    }
+
+    /// Get squares of the pieces which defend the piece on the `square`
+    pub fn defenders_of(&self, square: Square) -> Vec<Square> {
+        match self.board.map.get(&square) {
+            Some((_, side)) => self.attackers_of(square, side),
+            None => vec![],
+        }
+    }
+
+    /// Get available turns labeled by the way they escape the check
+    ///
+    /// Empty if the king of the player on turn is not in check.
+    pub fn check_evasions(&self) -> Vec<(CheckEvasionKind, &AvailableTurn)> {
+        let side = self.board.active_player;
+        let checkers =
+            self.attackers_of(self.king_square(side), side.opponent());
+        if checkers.is_empty() {
+            return vec![];
+        }
+
+        // Pawn captured en-passant is not on the destination square
+        let enpassant_victim =
+            self.board.enpassant.map(|e| (e.capture_pos, e.pawn_src));
+        let captured_square = |turn: &RawTurn| match enpassant_victim {
+            Some((dst, victim))
+                if turn.piece == Piece::Pawn && turn.dst == dst =>
+            {
+                victim
+            }
+            _ => turn.dst,
+        };
+
+        self.raw_turns()
+            .iter()
+            .zip(self.available_turns())
+            .map(|(raw, turn)| {
+                let kind = match raw.piece {
+                    Piece::King => CheckEvasionKind::KingMove,
+                    _ if raw.captured.is_some()
+                        && checkers.contains(&captured_square(raw)) =>
+                    {
+                        CheckEvasionKind::Capture
+                    }
+                    _ => CheckEvasionKind::Block,
+                };
+                (kind, turn)
+            })
+            .collect()
+    }
+
+    /// Get list of turns the player not on turn could play if it were their
+    /// turn
+    ///
+    /// En-passant is left out since it's only available to the player on turn.
+    pub fn opponent_turns(&self) -> Vec<AvailableTurn> {
+        let mut board = self.board.clone();
+        board.enpassant = None;
+        board.active_player.switch_side();
+
+        simulation::get_available_turns(&mut board)
+            .iter()
+            .map(AvailableTurn::from)
+            .collect()
+    }
+
+    /// Get the square of the `side`'s king
+    pub fn king_square(&self, side: Side) -> Square {
+        self.board.get_king_pos(side)
+    }
+
+    /// Get the square of the pawn which can be captured en-passant
+    pub fn en_passant_victim(&self) -> Option<Square> {
+        self.board.enpassant.map(|enpassant| enpassant.pawn_src)
+    }
+
+    /// Get the most recently played turn
+    pub fn last_move(&self) -> Option<&str> {
+        self.history.last().map(|state| state.get_turn())
+    }
+
+    /// Get the turn played at the given half move, counted from 0
+    pub fn move_at(&self, ply: usize) -> Option<&str> {
+        self.history.get(ply).map(|state| state.get_turn())
+    }
+
+    /// Iterate over the played turns in the order they were played
+    pub fn history(&self) -> impl Iterator<Item = MoveRecord> + '_ {
+        self.history.iter().map(MoveRecord::from)
+    }
+
+    /// Iterate over the time spent on every played turn
+    pub fn move_durations(
+        &self,
+    ) -> impl Iterator<Item = Option<Duration>> + '_ {
+        self.history.iter().map(|state| state.duration)
+    }
+
+    /// Check whether the player on turn has only a single legal turn
+    pub fn is_forced(&self) -> bool {
+        self.raw_turns().len() == 1
+    }
+
+    /// Get the only legal turn of the player on turn
+    pub fn only_move(&self) -> Option<&AvailableTurn> {
+        match self.is_forced() {
+            true => self.available_turns().first(),
+            _ => None,
+        }
+    }
+
+    /// Get list of available turns which capture a piece
+    pub fn capture_turns(&self) -> Vec<&AvailableTurn> {
+        self.available_turns()
+            .iter()
+            .filter(|turn| turn.captured.is_some())
+            .collect()
+    }
+
+    /// Get available turns grouped by the name of the moving piece and
+    /// sorted by their notation
+    pub fn available_turns_grouped(
+        &self,
+    ) -> BTreeMap<String, Vec<&AvailableTurn>> {
+        let mut grouped: BTreeMap<String, Vec<&AvailableTurn>> =
+            BTreeMap::new();
+        for turn in self.available_turns() {
+            grouped.entry(turn.piece.clone()).or_default().push(turn);
+        }
+
+        grouped
+            .values_mut()
+            .for_each(|turns| turns.sort_by_key(|turn| turn.get_turn()));
+        grouped
+    }
+
+    /// Get list of available turns which promote a pawn
+    pub fn promotion_turns(&self) -> Vec<&AvailableTurn> {
+        // Available turns are created out of the raw turns in the same order
+        self.raw_turns()
+            .iter()
+            .zip(self.available_turns())
+            .filter(|(raw, _)| raw.promotion().is_some())
+            .map(|(_, turn)| turn)
+            .collect()
+    }
+
+    /// Get list of available turns which give a check or a checkmate
+    pub fn checking_turns(&self) -> Vec<&AvailableTurn> {
+        self.available_turns()
+            .iter()
+            .filter(|turn| turn.get_turn().ends_with(['+', '#']))
+            .collect()
+    }
+
+    /// Get list of available turns which leave the opponent without legal
+    /// turns while not being in check
+    pub fn stalemate_in_one(&self) -> Vec<&AvailableTurn> {
+        let mut game = self.clone();
+        // Only the existence of opponent's turns matters
+        game.eager_move_generation = false;
+
+        self.available_turns()
+            .iter()
+            .filter(|turn| {
+                game.play_turn_strict(turn.get_turn())
+                    .expect("Available turn must be playable");
+                let side = game.board.active_player;
+                let king_pos = game.board.get_king_pos(side);
+                let stalemate = king::is_safe(&game.board.map, king_pos, side)
+                    && !simulation::has_legal_turn(&mut game.board);
+                game.undo_turn().expect("Turn was just played");
+                stalemate
+            })
+            .collect()
+    }
+
+    /// Get list of available turns which give a check by uncovering the line
+    /// of another piece
+    ///
+    /// Double checks, where the moving piece gives a check as well, are
+    /// included. Castling is left out since the rook always gives the check.
+    pub fn discovered_check_moves(&self) -> Vec<&AvailableTurn> {
+        let side = self.board.active_player;
+        let king = self.king_square(side.opponent());
+        let enpassant =
+            self.board.enpassant.map(|e| (e.capture_pos, e.pawn_src));
+
+        let is_discovered = |raw: &RawTurn| {
+            let castling = raw.piece == Piece::King
+                && (raw.src as u8).abs_diff(raw.dst as u8) == 2;
+            if castling {
+                return false;
+            }
+
+            let mut map = self.board.map;
+            map.remove(&raw.src);
+            map.insert(raw.dst, (raw.promotion().unwrap_or(raw.piece), side));
+            match enpassant {
+                Some((dst, victim))
+                    if raw.piece == Piece::Pawn && raw.dst == dst =>
+                {
+                    map.remove(&victim);
+                }
+                _ => (),
+            }
+
+            movement::attackers(&map, king, side)
+                .into_iter()
+                .any(|checker| checker != raw.dst)
+        };
+
+        self.raw_turns()
+            .iter()
+            .zip(self.available_turns())
+            .filter(|(_, turn)| turn.get_turn().ends_with(['+', '#']))
+            .filter(|(raw, _)| is_discovered(raw))
+            .map(|(_, turn)| turn)
+            .collect()
+    }
+
+    /// Get promotion squares of the pawn on `src` along with the pieces it
+    /// can be promoted to
+    pub fn promotion_options(&self, src: Square) -> Vec<(Square, Vec<Piece>)> {
+        let mut options = Vec::<(Square, Vec<Piece>)>::new();
+
+        for turn in self.raw_turns().iter().filter(|turn| turn.src == src) {
+            let promotion = match turn.promotion() {
+                Some(promotion) => promotion,
+                None => continue,
+            };
+
+            match options.iter_mut().find(|(dst, _)| *dst == turn.dst) {
+                Some((_, pieces)) => pieces.push(promotion),
+                None => options.push((turn.dst, vec![promotion])),
+            }
+        }
+
+        options
+    }
+
     /// Get list of available turns
     pub fn available_turns(&self) -> &Vec<AvailableTurn> {
-        &self.available_turns
+        self.available_turns.get_or_init(|| {
+            self.raw_turns().iter().map(AvailableTurn::from).collect()
+        })
+    }
+
+    /// Get the piece and its side on the given `square`
+    pub fn piece_at(&self, square: Square) -> Option<(Piece, Side)> {
+        self.board.map.get(&square)
+    }
+
+    /// Count pieces of a given type for a given side
+    pub fn piece_count(&self, side: Side, piece: Piece) -> usize {
+        self.board
+            .map
+            .into_iter()
+            .filter(|(_, figure)| *figure == (piece, side))
+            .count()
+    }
+
+    /// Get pieces of both sides written as e.g. `KRPvKR`
+    pub fn material_signature(&self) -> String {
+        let pieces = |side: Side| -> String {
+            [
+                Piece::King,
+                Piece::Queen,
+                Piece::Rook,
+                Piece::Bishop,
+                Piece::Knight,
+                Piece::Pawn,
+            ]
+            .into_iter()
+            .flat_map(|piece| {
+                let letter = fen::figure_char((piece, Side::White));
+                std::iter::repeat_n(letter, self.piece_count(side, piece))
+            })
+            .collect()
+        };
+
+        format!("{}v{}", pieces(Side::White), pieces(Side::Black))
+    }
+
+    /// Get squares and pieces of the `side`, ordered from `a8` to `h1`
+    pub fn pieces_of(&self, side: Side) -> Vec<(Square, Piece)> {
+        self.board
+            .map
+            .into_iter()
+            .filter(|(_, (_, s))| *s == side)
+            .map(|(square, (piece, _))| (square, piece))
+            .collect()
+    }
+
+    /// Get rules the game is played by
+    pub fn variant(&self) -> GameVariant {
+        self.board.variant
+    }
+
+    /// Get pieces in the pocket of the `side`, available for drops
+    pub fn pocket(&self, side: Side) -> Vec<Piece> {
+        self.board.pockets.pieces(side)
+    }
+
+    /// Get a copy of the encoded board squares, from `a8` to `h1`
+    pub fn board_bytes(&self) -> [u8; 64] {
+        self.board.map.to_bytes()
+    }
+
+    /// Count all pieces on the board
+    pub fn total_pieces(&self) -> usize {
+        self.board.map.len()
+    }
+
+    /// Count pieces of both sides on the board as `(white, black)`
+    pub fn piece_counts(&self) -> (usize, usize) {
+        self.board
+            .map
+            .fold((0, 0), |(white, black), (_, (_, side))| match side {
+                Side::White => (white + 1, black),
+                Side::Black => (white, black + 1),
+            })
+    }
+
+    /// Generate available turns one piece at a time, without calculating
+    /// them all upfront
+    pub fn generate_turns(&self) -> impl Iterator<Item = RawTurn> {
+        simulation::TurnGenerator::new(&self.board)
+    }
+
+    /// Get list of available turns without converting them into strings
+    pub fn available_turns_raw(&self) -> &Vec<RawTurn> {
+        self.raw_turns()
+    }
+
+    /// Get list of available turns in the long algebraic format used by UCI
+    pub fn available_moves_uci(&self) -> Vec<String> {
+        self.raw_turns().iter().map(RawTurn::to_uci).collect()
+    }
+
+    /// Get available turns, calculate them first if they are not known yet
+    fn raw_turns(&self) -> &Vec<RawTurn> {
+        self.raw_turns.get_or_init(|| match self.board.gamestate {
+            Gamestate::Ongoing => {
+                simulation::get_available_turns(&mut self.board.clone())
+            }
+            _ => vec![],
+        })
+    }
+
+    /// Check whether the player on turn has any legal turn
+    ///
+    /// Available turns are not calculated just to answer it.
+    fn has_available_turns(&mut self) -> bool {
+        match self.raw_turns.get() {
+            Some(raw_turns) => !raw_turns.is_empty(),
+            None => simulation::has_legal_turn(&mut self.board),
+        }
+    }
+
+    /// Replace available turns and drop their outdated string format
+    fn set_raw_turns(&mut self, raw_turns: Vec<RawTurn>) {
+        self.raw_turns = OnceLock::from(raw_turns);
+        self.available_turns = OnceLock::new();
+    }
+
+    /// Search for a forced mate within `max_depth` full moves
+    pub fn find_mate(&mut self, max_depth: usize) -> Option<Vec<String>> {
+        search::find_mate(self, max_depth)
+    }
+
+    /// Evaluate the position in centipawns from White's point of view
+    pub fn evaluate(&self) -> i32 {
+        evaluation::evaluate(self)
+    }
+
+    /// Suggest the best turn by looking `depth` half moves ahead
+    pub fn suggest_move(&mut self, depth: usize) -> Option<String> {
+        search::suggest_move(self, depth)
+    }
+
+    /// Get a turn which neither hangs material nor allows a mate in one
+    pub fn safe_hint(&self) -> Option<&AvailableTurn> {
+        search::safe_hint(self).and_then(|idx| self.available_turns().get(idx))
+    }
+
+    /// Count leaf nodes of the turn tree up to the given `depth`
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        perft::perft(self, depth)
+    }
+
+    /// Count leaf nodes of the turn tree under every root turn
+    pub fn perft_divide(&mut self, depth: usize) -> Vec<(String, u64)> {
+        perft::perft_divide(self, depth)
+    }
+
+    /// Count leaf nodes of the turn tree on multiple threads
+    #[cfg(feature = "rayon")]
+    pub fn perft_parallel(&self, depth: usize) -> u64 {
+        perft::perft_parallel(self, depth)
+    }
+
+    /// Check whether the `side` has more than a bare king or a king with a
+    /// single minor piece
+    pub fn has_mating_material(&self, side: Side) -> bool {
+        let pieces: Vec<Piece> = self
+            .board
+            .map
+            .into_iter()
+            .filter(|(_, (piece, s))| *s == side && *piece != Piece::King)
+            .map(|(_, (piece, _))| piece)
+            .collect();
+
+        !matches!(pieces.as_slice(), [] | [Piece::Knight] | [Piece::Bishop])
+    }
+
+    /// Check whether neither player can ever deliver a checkmate
+    ///
+    /// Covers K vs K, K+minor vs K, K+B vs K+B with the bishops on the same
+    /// color and K+N+N vs K. Any pawn, rook or queen on the board rules out
+    /// the draw, and so does the Crazyhouse variant.
+    pub fn is_theoretical_draw(&self) -> bool {
+        if self.board.variant == GameVariant::Crazyhouse {
+            return false;
+        }
+
+        let minors = |side: Side| -> Option<Vec<(Square, Piece)>> {
+            self.pieces_of(side)
+                .into_iter()
+                .filter(|(_, piece)| *piece != Piece::King)
+                .map(|(square, piece)| match piece {
+                    Piece::Knight | Piece::Bishop => Some((square, piece)),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let (Some(white), Some(black)) =
+            (minors(Side::White), minors(Side::Black))
+        else {
+            return false;
+        };
+
+        let light_square = |square: Square| {
+            let (file, rank) = square_to_coords(square);
+            (file + rank) % 2 == 1
+        };
+
+        match (white.as_slice(), black.as_slice()) {
+            ([], [])
+            | ([_], [])
+            | ([], [_])
+            | ([(_, Piece::Knight), (_, Piece::Knight)], [])
+            | ([], [(_, Piece::Knight), (_, Piece::Knight)]) => true,
+            ([(a, Piece::Bishop)], [(b, Piece::Bishop)]) => {
+                light_square(*a) == light_square(*b)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check whether the locked pawns and the kings can never change the
+    /// outcome of the game
+    pub fn is_dead_position(&self) -> bool {
+        self.board.variant != GameVariant::Crazyhouse
+            && deadposition::is_dead(&self.board)
+    }
+
+    /// Classify every file, from `a` to `h`, by the pawns standing on it
+    pub fn file_status(&self) -> [FileStatus; 8] {
+        let mut pawns = [(false, false); 8];
+        for (square, (piece, side)) in self.board.map {
+            if piece != Piece::Pawn {
+                continue;
+            }
+
+            let (file, _) = square_to_coords(square);
+            match side {
+                Side::White => pawns[file as usize].0 = true,
+                Side::Black => pawns[file as usize].1 = true,
+            }
+        }
+
+        pawns.map(|pawns| match pawns {
+            (false, false) => FileStatus::Open,
+            (false, true) => FileStatus::HalfOpenWhite,
+            (true, false) => FileStatus::HalfOpenBlack,
+            (true, true) => FileStatus::Closed,
+        })
+    }
+
+    /// Get squares of the pawns of the `side` which no enemy pawn can stop
+    /// on their way to promotion
+    ///
+    /// Enemy pawns ahead on the same or the neighboring files stop the pawn.
+    pub fn passed_pawns(&self, side: Side) -> Vec<Square> {
+        let pawns = |side: Side| {
+            self.board
+                .map
+                .into_iter()
+                .filter(move |(_, figure)| *figure == (Piece::Pawn, side))
+                .map(|(square, _)| square)
+        };
+
+        let stops = |pawn: Square, enemy: Square| {
+            let (file, rank) = square_to_coords(pawn);
+            let (enemy_file, enemy_rank) = square_to_coords(enemy);
+            let ahead = match side {
+                Side::White => enemy_rank > rank,
+                Side::Black => enemy_rank < rank,
+            };
+
+            ahead && file.abs_diff(enemy_file) <= 1
+        };
+
+        pawns(side)
+            .filter(|pawn| {
+                !pawns(side.opponent()).any(|enemy| stops(*pawn, enemy))
+            })
+            .collect()
+    }
+
+    /// Get doubled and isolated pawns of the `side`
+    pub fn pawn_structure(&self, side: Side) -> PawnStructure {
+        PawnStructure::new(&self.board.map, side)
     }
 
     /// Update gamestate for the draw conditions
     fn check_few_draw_conditions(&mut self) {
-        if self.available_turns.is_empty() {
-            self.board.gamestate = Gamestate::Stalemate;
+        if !self.has_available_turns() {
+            // Checkmate claimed only as a check still ends the game as a mate
+            let side = self.board.active_player;
+            let king_pos = self.board.get_king_pos(side);
+            self.board.gamestate =
+                match king::is_safe(&self.board.map, king_pos, side) {
+                    true => Gamestate::Stalemate,
+                    false => Gamestate::Checkmate(side.opponent().to_string()),
+                };
+            return;
+        }
+
+        self.check_insufficient_mating_material();
+    }
+
+    /// Update gamestate in case neither player is able to checkmate
+    fn check_insufficient_mating_material(&mut self) {
+        // Captured pieces never leave the Crazyhouse game
+        if !self.board.detect_draws
+            || self.board.variant == GameVariant::Crazyhouse
+        {
             return;
         }
 
         // Mating is not possible in the following conditions:
-        // -> K vs K    (len must be 2 for this condition)
+        // -> K vs K
         // -> K+B vs K
         // -> K+N vs K
-        if self.board.map.len() == 2
-            || (self.board.map.len() == 3
-                && self
-                    .board
-                    .map
-                    .into_iter()
-                    .map(|(_, (p, _))| p)
-                    .any(|p| p == Piece::Knight || p == Piece::Bishop))
+        if self.board.map.len() <= 3
+            && !self.has_mating_material(Side::White)
+            && !self.has_mating_material(Side::Black)
         {
             self.board.gamestate = Gamestate::DrawInsufficientMatingMaterial;
         }
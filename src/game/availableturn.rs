@@ -1,3 +1,5 @@
+use super::side::Side;
+use chess_notation_parser::Piece;
 use std::fmt;
 
 /// Data which describes the turn that can be played.
@@ -7,6 +9,7 @@ use std::fmt;
 ///
 /// Exact turn written in the algebraic chess notation format contains spoilers,
 /// so it should be fetched via `get_turn` function.
+#[derive(Clone)]
 pub struct AvailableTurn {
     /// Source square
     pub src: String,
@@ -20,6 +23,16 @@ pub struct AvailableTurn {
     /// Captured piece
     pub captured: Option<String>,
 
+    /// Captured piece along with its side
+    pub captured_piece: Option<(Piece, Side)>,
+
+    /// Part of the source square written in the turn to tell apart pieces of
+    /// the same type which can move to the same destination, e.g. `d` in
+    /// `Rdf8`, `1` in `R1a3` or `d1` in `Qd1f3`
+    ///
+    /// It's `None` for pawns and castling turns.
+    pub disambiguation: Option<String>,
+
     /// Chess notation format of the turn
     turn: String,
 }
@@ -49,6 +62,8 @@ impl AvailableTurn {
             dst,
             piece,
             captured,
+            captured_piece: None,
+            disambiguation: None,
             turn,
         }
     }
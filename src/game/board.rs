@@ -4,16 +4,23 @@ use super::castlinginfo;
 use super::enpassant::Enpassant;
 use super::game_error::GameError;
 use super::gamestate::Gamestate;
+use super::piecedrop::PieceDrop;
 use super::play;
+use super::pocket::Pockets;
 use super::side::Side;
 use super::state::State;
+use super::variant::GameVariant;
 use chess_notation_parser::{Castling, Move, Piece, Square, Turn};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
+/// Default number of half moves after which the *fifty move rule* applies
+pub const DEFAULT_FIFTY_MOVE_LIMIT: u8 = 100;
+
 /// Board tracks state of the game.
+#[derive(Clone)]
 pub struct Board {
     /// 64 squares containing all chess pieces
     pub map: BoardMap,
@@ -50,8 +57,38 @@ pub struct Board {
     /// - Pawn has moved
     pub fifty_move_rule: u8,
 
-    /// Stored hashes of every board state
-    pub state_hashes: BTreeMap<u64, u8>,
+    /// Number of half moves which ends the game by the *fifty move rule*
+    pub fifty_move_limit: u8,
+
+    /// Fifty move rule, threefold repetition and insufficient mating
+    /// material end the game only if this is set
+    pub detect_draws: bool,
+
+    /// Stored hashes of every board state since the last irreversible turn
+    ///
+    /// Counts are wider than `u8`, since positions repeat without limit when
+    /// draws are not detected.
+    pub state_hashes: BTreeMap<u64, u32>,
+
+    /// Hashes put aside by irreversible turns, needed again on undo
+    pub prev_state_hashes: Vec<BTreeMap<u64, u32>>,
+
+    /// Rules the game is played by
+    pub variant: GameVariant,
+
+    /// Captured pieces available for drops in the Crazyhouse variant
+    pub pockets: Pockets,
+
+    /// One bit per square holding a promoted piece, see `square_bit`
+    ///
+    /// Captured promoted pieces go into the pocket as pawns in the Crazyhouse
+    /// variant.
+    pub promoted: u64,
+}
+
+/// Bit of the `square` in the `Board::promoted` mask
+pub fn square_bit(square: Square) -> u64 {
+    1 << square as u64
 }
 
 impl Board {
@@ -84,6 +121,13 @@ impl Board {
             self.king.insert(side, dst);
         }
 
+        // Promoted piece stays promoted wherever it goes
+        let promoted = self.promoted & square_bit(src) != 0;
+        self.promoted &= !(square_bit(src) | square_bit(dst));
+        if promoted {
+            self.promoted |= square_bit(dst);
+        }
+
         self.map.insert(dst, (piece, side))
     }
 
@@ -99,9 +143,9 @@ impl Board {
         let hash = self.calc_hash();
         let hash_cnt = self.state_hashes.entry(hash).or_insert(0);
 
-        *hash_cnt += 1;
+        *hash_cnt = hash_cnt.saturating_add(1);
         match *hash_cnt {
-            3 => Gamestate::DrawThreeFoldRepetition,
+            3 if self.detect_draws => Gamestate::DrawThreeFoldRepetition,
             _ => self.gamestate.clone(),
         }
     }
@@ -115,21 +159,68 @@ impl Board {
         *self.state_hashes.entry(hash).or_insert(0) -= 1;
     }
 
+    /// Put aside stored hashes since none of those board states can occur
+    /// again after an irreversible turn, i.e. capture or pawn movement
+    pub fn clear_state_hashes(&mut self) {
+        let hashes = std::mem::take(&mut self.state_hashes);
+        self.prev_state_hashes.push(hashes);
+    }
+
+    /// Bring back hashes put aside by the undone irreversible turn
+    pub fn restore_state_hashes(&mut self) {
+        self.state_hashes = self
+            .prev_state_hashes
+            .pop()
+            .expect("Hashes are put aside for every irreversible turn");
+    }
+
+    /// Get how many times the current board state was stored
+    ///
+    /// Count saturates at `u8::MAX`.
+    pub fn repetition_count(&self) -> u8 {
+        // States are stored before the player on turn is switched
+        let hash = self.calc_hash_for(self.active_player.opponent());
+        let count = self.state_hashes.get(&hash).copied().unwrap_or(0);
+        u8::try_from(count).unwrap_or(u8::MAX)
+    }
+
+    /// Get hash of the position with the player on turn
+    pub fn position_key(&self) -> u64 {
+        self.calc_hash()
+    }
+
     /// Snapshot the board state into a hash value
     ///
     /// Hashed parts of the board are conditions used for threefold repetition
     /// rule
     fn calc_hash(&self) -> u64 {
+        self.calc_hash_for(self.active_player)
+    }
+
+    /// Snapshot the board state as if the `side` were on turn
+    fn calc_hash_for(&self, side: Side) -> u64 {
         let mut hasher = DefaultHasher::new();
 
-        self.active_player.hash(&mut hasher);
+        side.hash(&mut hasher);
         self.map.hash(&mut hasher);
         self.enpassant.hash(&mut hasher);
         self.castling_rights.hash(&mut hasher);
+        self.pockets.hash(&mut hasher);
 
         hasher.finish()
     }
 
+    /// Compare positions on both boards
+    ///
+    /// Compared parts of the board are the same ones used for hashing
+    pub fn same_position(&self, other: &Board) -> bool {
+        self.active_player == other.active_player
+            && self.map.same_pieces(&other.map)
+            && self.enpassant == other.enpassant
+            && self.castling_rights == other.castling_rights
+            && self.pockets == other.pockets
+    }
+
     /// Undo move based on the previous turn `State`
     ///
     /// Function does not modify active_player nor does it affect hash tree
@@ -140,11 +231,43 @@ impl Board {
         self.fifty_move_rule = prev_state.fifty_move_rule;
         self.gamestate = Gamestate::Ongoing;
 
+        // Null turn doesn't touch any piece
+        if prev_state.get_turn() == play::NULL_TURN {
+            return;
+        }
+
+        // Dropped piece is just removed, pockets are restored separately
+        if let Ok(turn) = PieceDrop::try_from(prev_state.get_turn()) {
+            self.map.remove(&turn.dst);
+            return;
+        }
+
         // Undone turn was already parsed successfully before, so unwrap!
+        let promoted = prev_state.promoted;
         match Turn::try_from(prev_state.get_turn()).unwrap() {
             Turn::Castling(turn) => self.undo_castling(turn),
             Turn::Move(turn) => self.undo_move(turn, prev_state),
         }
+        self.promoted = promoted;
+    }
+
+    /// Restore pockets changed by the turn which is about to be undone
+    ///
+    /// Simulated turns leave the pockets intact, so only the played turns
+    /// need this.
+    pub fn undo_pockets(&mut self, prev_state: &State) {
+        if self.variant != GameVariant::Crazyhouse {
+            return;
+        }
+
+        let side = self.active_player;
+        if let Ok(turn) = PieceDrop::try_from(prev_state.get_turn()) {
+            self.pockets.add(side, turn.piece);
+        }
+
+        if let Some(piece) = prev_state.pocketed_piece() {
+            self.pockets.remove(side, piece);
+        }
     }
 
     fn undo_castling(&mut self, turn: Castling) {
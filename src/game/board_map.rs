@@ -71,6 +71,55 @@ fn u8_from_figure(piece: Piece, side: Side) -> u8 {
     side + piece
 }
 
+/// Convert index used by the `BoardMap` into a `Square`
+///
+/// Index goes from `a8` (0) to `h8` (7) and continues rank by rank down to
+/// `h1` (63).
+pub fn index_to_square(idx: u8) -> Option<Square> {
+    match idx < ARR_LEN {
+        true => Some(Square::from(idx)),
+        _ => None,
+    }
+}
+
+/// Convert a `Square` into zero-based `(file, rank)` coordinates
+///
+/// File `a` and the 1st rank are both 0, so `e4` becomes `(4, 3)`.
+pub fn square_to_coords(square: Square) -> (u8, u8) {
+    let idx = square as u8;
+    (idx % 8, 7 - idx / 8)
+}
+
+/// Convert zero-based `(file, rank)` coordinates into a `Square`
+pub fn coords_to_square(file: u8, rank: u8) -> Option<Square> {
+    match file < 8 && rank < 8 {
+        true => Some(Square::from((7 - rank) * 8 + file)),
+        _ => None,
+    }
+}
+
+/// Get squares lying strictly between `a` and `b`, ordered from `a` to `b`
+///
+/// Squares which don't share a rank, a file or a diagonal have no squares
+/// between them.
+pub fn squares_between(a: Square, b: Square) -> Vec<Square> {
+    let (a_file, a_rank) = square_to_coords(a);
+    let (b_file, b_rank) = square_to_coords(b);
+    let x = b_file as i8 - a_file as i8;
+    let y = b_rank as i8 - a_rank as i8;
+
+    if x != 0 && y != 0 && x.abs() != y.abs() {
+        return vec![];
+    }
+
+    let step = |square: &Square| {
+        square.get_relative_neighbor(x.signum(), y.signum()).ok()
+    };
+    std::iter::successors(step(&a), step)
+        .take_while(|square| *square != b)
+        .collect()
+}
+
 impl Iterator for BoardMap {
     type Item = (Square, (Piece, Side));
 
@@ -108,6 +157,33 @@ impl BoardMap {
         }
     }
 
+    /// Returns a copy of all 64 encoded squares.
+    pub fn to_bytes(self) -> [u8; ARR_LEN as usize] {
+        self.arr
+    }
+
+    /// Creates a `BoardMap` out of the encoded squares.
+    ///
+    /// Returns `None` if any of the bytes is not a valid encoded square.
+    pub fn from_bytes(bytes: [u8; ARR_LEN as usize]) -> Option<Self> {
+        let mut map = Self::new();
+
+        for (idx, data) in bytes.into_iter().enumerate() {
+            if data == 0 {
+                continue;
+            }
+
+            let (piece, side) = u8_to_figure(data);
+            if u8_from_figure(piece, side) != data {
+                return None;
+            }
+
+            map.insert(Square::from(idx as u8), (piece, side));
+        }
+
+        Some(map)
+    }
+
     /// Returns a value corresponding to the key.
     pub fn get(&self, square: &Square) -> Option<(Piece, Side)> {
         match self.arr[*square as usize] {
@@ -158,12 +234,35 @@ impl BoardMap {
     pub fn len(&self) -> usize {
         self.len as usize
     }
+
+    /// Check whether both maps have the same pieces on the same squares
+    pub fn same_pieces(&self, other: &BoardMap) -> bool {
+        self.arr == other.arr
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn between() {
+        let names = |a, b| -> Vec<String> {
+            squares_between(a, b)
+                .iter()
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        assert_eq!(names(Square::A1, Square::A4), ["a2", "a3"]);
+        assert_eq!(names(Square::H5, Square::E5), ["g5", "f5"]);
+        assert_eq!(names(Square::C1, Square::H6), ["d2", "e3", "f4", "g5"]);
+        assert_eq!(names(Square::B7, Square::D5), ["c6"]);
+        assert!(names(Square::E4, Square::E5).is_empty());
+        assert!(names(Square::E4, Square::E4).is_empty());
+        assert!(names(Square::G1, Square::F3).is_empty());
+    }
+
     #[test]
     fn iterator() {
         let mut map = BoardMap::new();
@@ -180,4 +279,59 @@ mod tests {
             continue;
         }
     }
+
+    #[test]
+    fn bytes() {
+        let mut map = BoardMap::new();
+        map.insert(Square::A8, (Piece::Rook, Side::Black));
+        map.insert(Square::E1, (Piece::King, Side::White));
+
+        let bytes = map.to_bytes();
+        assert_eq!(bytes[Square::A8 as usize], SIDE_BLACK | PIECE_ROOK);
+        assert_eq!(bytes[Square::E1 as usize], SIDE_WHITE | PIECE_KING);
+        assert_eq!(bytes.iter().filter(|b| **b != 0).count(), 2);
+
+        let decoded = BoardMap::from_bytes(bytes).unwrap();
+        assert!(decoded.same_pieces(&map));
+        assert_eq!(decoded.len(), 2);
+
+        for invalid in [PIECE_ROOK, SIDE_WHITE, SIDE_WHITE | 7, MASK_SIDES | 1]
+        {
+            let mut bytes = bytes;
+            bytes[0] = invalid;
+            assert!(BoardMap::from_bytes(bytes).is_none());
+        }
+    }
+
+    #[test]
+    fn square_index() {
+        assert_eq!(index_to_square(0).unwrap().to_string(), "a8");
+        assert_eq!(index_to_square(7).unwrap().to_string(), "h8");
+        assert_eq!(index_to_square(56).unwrap().to_string(), "a1");
+        assert_eq!(index_to_square(63).unwrap().to_string(), "h1");
+        assert!(index_to_square(64).is_none());
+
+        // Iterator must use the same indexing
+        let mut map = BoardMap::new();
+        map.insert(index_to_square(12).unwrap(), (Piece::Pawn, Side::Black));
+        let (square, _) = map.into_iter().next().unwrap();
+        assert_eq!(square.to_string(), "e7");
+    }
+
+    #[test]
+    fn square_coords() {
+        assert_eq!(square_to_coords(Square::A1), (0, 0));
+        assert_eq!(square_to_coords(Square::E4), (4, 3));
+        assert_eq!(square_to_coords(Square::H8), (7, 7));
+
+        assert_eq!(coords_to_square(0, 7).unwrap().to_string(), "a8");
+        assert_eq!(coords_to_square(7, 0).unwrap().to_string(), "h1");
+        assert!(coords_to_square(8, 0).is_none());
+        assert!(coords_to_square(0, 8).is_none());
+
+        for idx in 0..64 {
+            let (file, rank) = square_to_coords(Square::from(idx));
+            assert_eq!(coords_to_square(file, rank).unwrap() as u8, idx);
+        }
+    }
 }
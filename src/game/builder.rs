@@ -1,14 +1,19 @@
-use super::board::Board;
+use super::board::{Board, DEFAULT_FIFTY_MOVE_LIMIT};
 use super::board_map::BoardMap;
 use super::castling_rights::{CastlingRights, StartingRights};
+use super::fen;
 use super::gamestate::Gamestate;
+use super::king;
+use super::pocket::Pockets;
 use super::side::Side;
 use super::simulation;
 use super::state::State;
+use super::variant::GameVariant;
 use super::Game;
 use chess_notation_parser::{Piece, Square};
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Create a board using a `setup` string
 ///
@@ -22,25 +27,103 @@ pub fn create(setup: &'static str) -> Result<Game, &'static str> {
         _ => CastlingRights::new(StartingRights::All),
     };
 
-    let tree: BTreeMap<u64, u8> = BTreeMap::new();
-    let mut board = Board {
+    create_from_map(map, king, castling_rights, Side::White)
+}
+
+/// Create a board out of the encoded squares, see `BoardMap::to_bytes`
+///
+/// Castling and en-passant are not available in such a setup.
+pub fn create_from_bytes(
+    bytes: [u8; 64],
+    active_player: Side,
+) -> Result<Game, &'static str> {
+    let map = BoardMap::from_bytes(bytes).ok_or("Invalid encoded square")?;
+    let mut king = HashMap::<Side, Square>::with_capacity(2);
+
+    for (square, (piece, side)) in map {
+        if piece == Piece::King && king.insert(side, square).is_some() {
+            return Err("Player cannot have more than one king");
+        }
+    }
+
+    if king.len() != 2 {
+        return Err("Game needs to have two kings");
+    }
+
+    let castling_rights = CastlingRights::new(StartingRights::None);
+    create_from_map(map, king, castling_rights, active_player)
+}
+
+/// Create a game out of the validated piece placement
+fn create_from_map(
+    map: BoardMap,
+    king: HashMap<Side, Square>,
+    castling_rights: CastlingRights,
+    active_player: Side,
+) -> Result<Game, &'static str> {
+    let tree: BTreeMap<u64, u32> = BTreeMap::new();
+    let board = Board {
         map,
         enpassant: None,
         king,
         castling_rights,
-        active_player: Side::White,
+        active_player,
         gamestate: Gamestate::Ongoing,
         fifty_move_rule: 0,
+        fifty_move_limit: DEFAULT_FIFTY_MOVE_LIMIT,
+        detect_draws: true,
         state_hashes: tree,
+        prev_state_hashes: vec![],
+        variant: GameVariant::Standard,
+        pockets: Pockets::default(),
+        promoted: 0,
     };
 
-    let available_turns = simulation::get_available_turns(&mut board);
+    if !is_idle_king_safe(&board) {
+        return Err("King of the player not on turn must not be in check");
+    }
 
-    Ok(Game {
+    Ok(create_from_board(board))
+}
+
+/// Create a game with an empty turn history out of a prepared board
+pub fn create_from_board(mut board: Board) -> Game {
+    let setup_fen = fen::export(&board, 1);
+    let raw_turns = match board.gamestate {
+        Gamestate::Ongoing => simulation::get_available_turns(&mut board),
+        _ => vec![],
+    };
+
+    let mut game = Game {
         history: Vec::<State>::with_capacity(128),
+        raw_turns_history: Vec::with_capacity(128),
         board,
-        available_turns,
-    })
+        raw_turns: OnceLock::from(raw_turns),
+        eager_move_generation: true,
+        available_turns: OnceLock::new(),
+        clock: None,
+        clock_history: Vec::with_capacity(128),
+        setup_fen,
+        redo_turns: vec![],
+    };
+
+    // Setup might already be over or not have enough pieces to play for a win
+    if game.board.gamestate == Gamestate::Ongoing {
+        game.check_few_draw_conditions();
+        if game.board.gamestate != Gamestate::Ongoing {
+            game.set_raw_turns(vec![]);
+        }
+    }
+
+    game
+}
+
+/// Check that the player not on turn has not left its king in check
+///
+/// Such a position cannot be reached by playing legal turns.
+pub fn is_idle_king_safe(board: &Board) -> bool {
+    let side = board.active_player.opponent();
+    king::is_safe(&board.map, board.get_king_pos(side), side)
 }
 
 /// Setup the board using `setup` argument
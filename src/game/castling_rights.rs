@@ -11,7 +11,7 @@ pub enum StartingRights {
 }
 
 /// Struct which keeps track of which castling is possible
-#[derive(Hash, Copy, Clone)]
+#[derive(Hash, Copy, Clone, PartialEq)]
 pub struct CastlingRights {
     /// All info can fit within 8 bits
     bitmask: u8,
@@ -87,6 +87,11 @@ impl CastlingRights {
         true
     }
 
+    /// Returns true if castling right is available
+    pub fn contains(&self, (side, castling): &(Side, CastlingType)) -> bool {
+        Self::get_mask((*side, *castling)) & self.bitmask != 0
+    }
+
     /// Returns true if no castling rights available
     pub fn is_empty(&self) -> bool {
         self.bitmask == 0
@@ -114,7 +119,9 @@ mod tests {
         let castling = (Side::White, CastlingType::Long);
         assert_eq!(map.insert(castling), false);
         assert_eq!(map.insert(castling), true);
+        assert!(map.contains(&castling));
         assert_eq!(map.remove(&castling), true);
+        assert!(!map.contains(&castling));
         assert_eq!(map.remove(&castling), false);
     }
 }
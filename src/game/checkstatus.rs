@@ -0,0 +1,12 @@
+/// Situation of the king of the player on turn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// King is not under attack
+    Safe,
+
+    /// King is under attack, but the check can be stopped
+    Check,
+
+    /// King is under attack and no legal turn stops the check
+    Checkmate,
+}
@@ -0,0 +1,80 @@
+use super::side::Side;
+use std::time::Duration;
+
+/// Chess clock with the remaining time of both players
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clock {
+    /// Remaining time of the White player
+    white: Duration,
+
+    /// Remaining time of the Black player
+    black: Duration,
+
+    /// Time added to the player's clock after every turn
+    increment: Duration,
+}
+
+impl Clock {
+    /// Create a clock where both players start with the same amount of time
+    pub fn new(time: Duration, increment: Duration) -> Self {
+        Self {
+            white: time,
+            black: time,
+            increment,
+        }
+    }
+
+    /// Get remaining time of the `side`
+    pub fn time_remaining(&self, side: Side) -> Duration {
+        match side {
+            Side::White => self.white,
+            Side::Black => self.black,
+        }
+    }
+
+    /// Get time added to the player's clock after every turn
+    pub fn increment(&self) -> Duration {
+        self.increment
+    }
+
+    /// Subtract time spent on the turn and add the increment.
+    ///
+    /// Returns `false` in case the `side` has run out of time (flag fall).
+    pub(crate) fn spend(&mut self, side: Side, elapsed: Duration) -> bool {
+        let increment = self.increment;
+        let remaining = match side {
+            Side::White => &mut self.white,
+            Side::Black => &mut self.black,
+        };
+
+        match remaining.checked_sub(elapsed) {
+            Some(left) if !left.is_zero() => {
+                *remaining = left + increment;
+                true
+            }
+            _ => {
+                *remaining = Duration::ZERO;
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spend() {
+        let mut clock =
+            Clock::new(Duration::from_secs(60), Duration::from_secs(2));
+
+        assert!(clock.spend(Side::White, Duration::from_secs(10)));
+        assert_eq!(clock.time_remaining(Side::White), Duration::from_secs(52));
+        assert_eq!(clock.time_remaining(Side::Black), Duration::from_secs(60));
+
+        // Running out of time exactly is a flag fall as well
+        assert!(!clock.spend(Side::Black, Duration::from_secs(60)));
+        assert_eq!(clock.time_remaining(Side::Black), Duration::ZERO);
+    }
+}
@@ -0,0 +1,155 @@
+use super::board::Board;
+use super::board_map::BoardMap;
+use super::side::Side;
+use chess_notation_parser::{Piece, Square};
+
+/// King steps in all eight directions
+const KING_STEPS: [(i8, i8); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Check whether the position can never change into a won one
+///
+/// Only kings and pawns may be on the board. Every pawn must be blocked by
+/// another pawn and have nothing to capture, so the pawns never move again.
+/// Then kings are the only moving pieces and no king may reach an enemy pawn
+/// which is not protected by another pawn. Some dead positions are missed,
+/// but a position reported as dead is always dead.
+pub fn is_dead(board: &Board) -> bool {
+    let map = board.map;
+
+    if board.enpassant.is_some()
+        || map
+            .into_iter()
+            .any(|(_, (piece, _))| !matches!(piece, Piece::Pawn | Piece::King))
+    {
+        return false;
+    }
+
+    let pawns: Vec<(Square, Side)> = map
+        .into_iter()
+        .filter(|(_, (piece, _))| *piece == Piece::Pawn)
+        .map(|(square, (_, side))| (square, side))
+        .collect();
+
+    let is_pawn = |square: Result<Square, _>| {
+        matches!(
+            square.map(|square| map.get(&square)),
+            Ok(Some((Piece::Pawn, _)))
+        )
+    };
+
+    for (square, side) in &pawns {
+        let forward = pawn_forward(*side);
+        if !is_pawn(square.get_relative_neighbor(0, forward)) {
+            return false;
+        }
+
+        let can_capture = [-1, 1].into_iter().any(|x| {
+            square
+                .get_relative_neighbor(x, forward)
+                .is_ok_and(|target| {
+                    map.get(&target) == Some((Piece::Pawn, side.opponent()))
+                })
+        });
+        if can_capture {
+            return false;
+        }
+    }
+
+    [Side::White, Side::Black]
+        .into_iter()
+        .all(|side| !king_reaches_pawn(&map, &pawns, side))
+}
+
+/// Direction in which pawns of the `side` move along the files
+fn pawn_forward(side: Side) -> i8 {
+    match side {
+        Side::White => 1,
+        Side::Black => -1,
+    }
+}
+
+/// Check whether the king of the `side` can walk to an enemy pawn and
+/// capture it
+///
+/// Only the pawns block the king, the enemy king is ignored.
+fn king_reaches_pawn(
+    map: &BoardMap,
+    pawns: &[(Square, Side)],
+    side: Side,
+) -> bool {
+    let mut attacked = [false; 64];
+    for (square, _) in pawns.iter().filter(|(_, s)| *s == side.opponent()) {
+        for x in [-1, 1] {
+            if let Ok(target) =
+                square.get_relative_neighbor(x, pawn_forward(side.opponent()))
+            {
+                attacked[target as usize] = true;
+            }
+        }
+    }
+
+    let king = map
+        .into_iter()
+        .find(|(_, figure)| *figure == (Piece::King, side))
+        .map(|(square, _)| square)
+        .expect("King must be on the board");
+
+    let mut visited = [false; 64];
+    visited[king as usize] = true;
+    let mut stack = vec![king];
+
+    while let Some(square) = stack.pop() {
+        for (x, y) in KING_STEPS {
+            let Ok(next) = square.get_relative_neighbor(x, y) else {
+                continue;
+            };
+
+            if visited[next as usize] || attacked[next as usize] {
+                continue;
+            }
+            visited[next as usize] = true;
+
+            match map.get(&next) {
+                Some((Piece::Pawn, s)) if s == side => (),
+                Some((Piece::Pawn, _)) => return true,
+                _ => stack.push(next),
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fen;
+    use super::*;
+
+    fn dead(fen: &str) -> bool {
+        is_dead(&fen::parse(fen).unwrap())
+    }
+
+    #[test]
+    fn blocked_pawns() {
+        assert!(dead("8/8/1k6/p1p1p1p1/P1P1P1P1/8/8/3K4 w - - 0 1"));
+        assert!(dead("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+
+        // Pawn on the open g-file can advance
+        assert!(!dead("8/8/1k6/p1p1p3/P1P1P1P1/8/8/3K4 w - - 0 1"));
+        // King walks through the open g-file to the e5 pawn
+        assert!(!dead("8/8/1k6/p1p1p3/P1P1P3/8/8/3K4 w - - 0 1"));
+        // Pawn capture is available
+        assert!(!dead("8/8/1k6/p1p1p1p1/P1P1PPP1/8/8/3K4 w - - 0 1"));
+        // Pieces other than pawns
+        assert!(!dead("8/8/1k6/p1p1p1p1/P1P1P1P1/8/8/3K3B w - - 0 1"));
+    }
+}
@@ -1,4 +1,5 @@
-use super::super::{DisplayOption, ViewMode};
+use super::super::{ColorScheme, DisplayOption, ViewMode};
+use super::evaluation;
 use super::side::Side;
 use super::state::State;
 use super::Game;
@@ -8,11 +9,21 @@ use chess_notation_parser::{Piece, Square};
 pub fn display_game(game: &Game, opt: DisplayOption) -> String {
     match opt {
         DisplayOption::BoardView(ViewMode::FancyTui) => {
-            tui_fancy::display_board_fancy(game)
+            tui_fancy::display_board_fancy(game, &ColorScheme::default())
+        }
+        DisplayOption::BoardView(ViewMode::FancyTuiColors(colors)) => {
+            tui_fancy::display_board_fancy(game, &colors)
+        }
+        DisplayOption::BoardView(view_mode) => {
+            tui_ascii::display_board_ascii(game, view_mode)
         }
-        DisplayOption::BoardView(_) => tui_ascii::display_board_ascii(game),
         DisplayOption::TurnHistory => display_history(&game.history),
+        DisplayOption::Movetext => display_movetext(game),
         DisplayOption::CaptureHistory => display_captured(&game.history),
+        DisplayOption::CaptureHistoryBySide => {
+            display_captured_by_side(&game.history)
+        }
+        DisplayOption::Html => html::display_board_html(game),
     }
 }
 
@@ -61,15 +72,49 @@ fn display_history(history: &Vec<State>) -> String {
     s
 }
 
+/// Print turn history as space separated movetext, e.g. `1. e4 e5 2. Nf3`
+///
+/// Numbering continues from the setup, a game started by Black begins with
+/// e.g. `1... e5`.
+fn display_movetext(game: &Game) -> String {
+    let (fullmove, black_started) = game.setup_fullmove();
+    let mut movetext = Vec::with_capacity(game.history.len() * 3 / 2 + 1);
+
+    for (i, state) in game.history.iter().enumerate() {
+        let ply = i + black_started as usize;
+        let number = fullmove + ply / 2;
+
+        match (ply % 2, i) {
+            (0, _) => movetext.push(format!("{}.", number)),
+            (_, 0) => movetext.push(format!("{}...", number)),
+            _ => (),
+        }
+        movetext.push(state.get_turn().to_string());
+    }
+
+    movetext.join(" ")
+}
+
 /// Print captured pieces
 fn display_captured(history: &Vec<State>) -> String {
     if history.is_empty() {
         return String::new();
     }
 
+    // Points of the pieces captured by White and Black
+    let mut points_white = 0;
+    let mut points_black = 0;
+
     let mut s = String::from('\n');
     for (i, state) in history.iter().enumerate() {
         if let Some((_, captured)) = state.captured {
+            let (piece, side) = captured;
+            let points = evaluation::piece_value(piece) / 100;
+            match side {
+                Side::Black => points_white += points,
+                Side::White => points_black += points,
+            }
+
             s.push_str(
                 format!(
                     "{} ({}: {})\n",
@@ -82,21 +127,52 @@ fn display_captured(history: &Vec<State>) -> String {
         }
     }
 
+    s.push_str(&format!(
+        "\nPoints captured - White: {}, Black: {}\n",
+        points_white, points_black
+    ));
+    s.push_str(&match points_white - points_black {
+        0 => String::from("Material: even\n"),
+        diff if diff > 0 => format!("Material: White +{}\n", diff),
+        diff => format!("Material: Black +{}\n", -diff),
+    });
+
     s.push('\n');
     s
 }
 
+/// Print captured pieces in separate sections for each capturing player
+fn display_captured_by_side(history: &[State]) -> String {
+    if history.is_empty() {
+        return String::new();
+    }
+
+    let mut captured_by_white = String::new();
+    let mut captured_by_black = String::new();
+
+    for (_, captured) in history.iter().filter_map(|state| state.captured) {
+        let section = match captured.1 {
+            Side::Black => &mut captured_by_white,
+            Side::White => &mut captured_by_black,
+        };
+
+        section.push(' ');
+        section.push(print_piece(captured));
+    }
+
+    format!(
+        "\nCaptured by White:{}\nCaptured by Black:{}\n\n",
+        captured_by_white, captured_by_black
+    )
+}
+
 mod tui_fancy {
     use super::*;
 
-    const C_WHITE: &str = "\x1b[40m\x1b[37m";
-    const C_BLACK: &str = "\x1b[47m\x1b[30m";
     const C_RESET: &str = "\x1b[40m\x1b[0m";
-    const C_FG_RED: &str = "\x1b[1;37m";
-    const C_GRID: &str = "\x1b[44m";
 
     /// Print board with a colorful format
-    pub fn display_board_fancy(game: &Game) -> String {
+    pub fn display_board_fancy(game: &Game, colors: &ColorScheme) -> String {
         let mut s = String::new();
 
         const GRID_UNIT_LEN: usize = 3;
@@ -106,20 +182,24 @@ mod tui_fancy {
         s.push_str("\x1B[2J\x1B[1;1H");
 
         // Top grid
-        s.push_str(&format!("{}{}\n", C_GRID, " ".repeat(GRID_LEN)));
+        s.push_str(&format!("{}{}\n", colors.grid, " ".repeat(GRID_LEN)));
 
         for rank in ('1'..='8').rev() {
             // Print 'rank' letter in front of every row
-            s.push_str(&format!("{} {}{} ", C_GRID, C_FG_RED, rank));
+            s.push_str(&format!("{} {}{} ", colors.grid, colors.labels, rank));
 
             for file in 'a'..='h' {
-                s.push_str(&print_square(rank, file, game));
+                s.push_str(&print_square(rank, file, game, colors));
             }
-            s.push_str(&format!("{}{}\n", C_GRID, " ".repeat(GRID_UNIT_LEN)));
+            s.push_str(&format!(
+                "{}{}\n",
+                colors.grid,
+                " ".repeat(GRID_UNIT_LEN)
+            ));
         }
 
         // Print 'file' letter at bottom of every file/column
-        s.push_str(&format!("{}{}", C_GRID, " ".repeat(GRID_UNIT_LEN)));
+        s.push_str(&format!("{}{}", colors.grid, " ".repeat(GRID_UNIT_LEN)));
         for file in 'a'..='h' {
             s.push_str(&format!(" {} ", file));
         }
@@ -128,12 +208,17 @@ mod tui_fancy {
         s
     }
 
-    fn print_square(rank: char, file: char, game: &Game) -> String {
+    fn print_square(
+        rank: char,
+        file: char,
+        game: &Game,
+        colors: &ColorScheme,
+    ) -> String {
         let square = construct_square(rank, file);
 
         let color = match (rank as u8 + file as u8) % 2 {
-            0 => C_BLACK,
-            _ => C_WHITE,
+            0 => colors.dark_square,
+            _ => colors.light_square,
         };
 
         let piece = match game.board.map.get(&square) {
@@ -148,25 +233,40 @@ mod tui_fancy {
 mod tui_ascii {
     use super::*;
 
-    pub fn display_board_ascii(game: &Game) -> String {
-        let mut s = String::with_capacity(256);
+    pub fn display_board_ascii(game: &Game, view_mode: ViewMode) -> String {
+        let mut s = String::with_capacity(320);
+        let bordered = matches!(view_mode, ViewMode::SimpleAsciiBordered);
+
+        if bordered {
+            s.push_str(&print_files());
+            s.push('\n');
+        }
 
         for rank in ('1'..='8').rev() {
             // Print 'rank' letter in front of every row
             s.push_str(&format!("{} ", rank));
 
             for file in 'a'..='h' {
-                s.push_str(&print_square(rank, file, game));
+                s.push_str(&print_square(rank, file, game, view_mode));
+            }
+
+            // Bordered board has it at the end of the row as well
+            if bordered {
+                s.push(rank);
             }
             s.push('\n');
         }
 
         // Print 'file' letter at bottom of every file/column
-        s.push_str("  ");
+        s.push_str(&print_files());
+        s
+    }
+
+    fn print_files() -> String {
+        let mut s = String::from("  ");
         for file in 'a'..='h' {
             s.push_str(&format!(" {} ", file));
         }
-
         s
     }
 
@@ -188,7 +288,12 @@ mod tui_ascii {
         }
     }
 
-    fn print_square(rank: char, file: char, game: &Game) -> String {
+    fn print_square(
+        rank: char,
+        file: char,
+        game: &Game,
+        view_mode: ViewMode,
+    ) -> String {
         let mut s = String::with_capacity(10);
         let square = construct_square(rank, file);
 
@@ -201,13 +306,61 @@ mod tui_ascii {
                 ));
             }
             None => {
-                let empty_square = match (rank as u8 + file as u8) % 2 {
-                    0 => " + ",
-                    _ => " - ",
-                };
+                let empty_square =
+                    match (view_mode, (rank as u8 + file as u8) % 2) {
+                        (ViewMode::SimpleAsciiPlain, _) => " . ",
+                        (_, 0) => " + ",
+                        _ => " - ",
+                    };
                 s.push_str(empty_square);
             }
         }
         s
     }
 }
+
+mod html {
+    use super::*;
+
+    const C_LIGHT: &str = "#f0d9b5";
+    const C_DARK: &str = "#b58863";
+    const CELL_STYLE: &str = "width: 48px; height: 48px; text-align: center; \
+                              font-size: 32px;";
+
+    /// Print board as an HTML table
+    pub fn display_board_html(game: &Game) -> String {
+        let mut s =
+            String::from("<table style=\"border-collapse: collapse;\">\n");
+
+        for rank in ('1'..='8').rev() {
+            s.push_str("  <tr>\n");
+            for file in 'a'..='h' {
+                s.push_str(&print_square(rank, file, game));
+            }
+            s.push_str("  </tr>\n");
+        }
+
+        s.push_str("</table>\n");
+        s
+    }
+
+    fn print_square(rank: char, file: char, game: &Game) -> String {
+        let square = construct_square(rank, file);
+
+        let color = match (rank as u8 + file as u8) % 2 {
+            0 => C_DARK,
+            _ => C_LIGHT,
+        };
+
+        let piece = match game.board.map.get(&square) {
+            Some((piece, side)) => print_piece((piece, side)).to_string(),
+            None => String::new(),
+        };
+
+        format!(
+            "    <td data-square=\"{}\" \
+             style=\"background-color: {}; {}\">{}</td>\n",
+            square, color, CELL_STYLE, piece
+        )
+    }
+}
@@ -2,7 +2,7 @@ use super::side::Side;
 use chess_notation_parser::Square;
 
 /// Info required for a possible en-passant turn
-#[derive(Hash, Copy, Clone)]
+#[derive(Hash, Copy, Clone, PartialEq)]
 pub struct Enpassant {
     /// Location of the pawn which can be captured
     pub pawn_src: Square,
@@ -0,0 +1,164 @@
+use super::board::Board;
+use super::fen;
+
+/// Operations of an EPD record, e.g. `bm Qg6; id "WAC.001";`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EpdOps {
+    /// Opcodes with their operands in the order of appearance
+    operations: Vec<(String, Vec<String>)>,
+}
+
+impl EpdOps {
+    /// Get operands of the operation with the given `opcode`
+    pub fn get(&self, opcode: &str) -> Option<&[String]> {
+        self.operations
+            .iter()
+            .find(|(op, _)| op == opcode)
+            .map(|(_, operands)| operands.as_slice())
+    }
+
+    /// Get best moves, listed with the `bm` opcode
+    pub fn best_moves(&self) -> &[String] {
+        self.get("bm").unwrap_or_default()
+    }
+
+    /// Get moves to avoid, listed with the `am` opcode
+    pub fn avoid_moves(&self) -> &[String] {
+        self.get("am").unwrap_or_default()
+    }
+
+    /// Get position identifier, listed with the `id` opcode
+    pub fn id(&self) -> Option<&str> {
+        self.get("id")?.first().map(String::as_str)
+    }
+
+    /// Iterate over all operations in the order of appearance
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.operations
+            .iter()
+            .map(|(op, operands)| (op.as_str(), operands.as_slice()))
+    }
+}
+
+/// Create a board out of an EPD record and collect its operations
+///
+/// Example: `6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id "mate";`
+///
+/// Halfmove clock is taken from the `hmvc` operation if present.
+pub fn parse(epd: &str) -> Result<(Board, EpdOps), String> {
+    let mut rest = epd;
+    let mut fields = [""; 4];
+
+    for (field, name) in fields.iter_mut().zip([
+        "piece placement",
+        "side to move",
+        "castling",
+        "en-passant",
+    ]) {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Err(format!("EPD: Missing {} field", name));
+        }
+
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        (*field, rest) = rest.split_at(end);
+    }
+
+    let [placement, side, castling, enpassant] = fields;
+    let mut board = fen::parse_position(placement, side, castling, enpassant)?;
+    let ops = parse_operations(rest)?;
+
+    if let Some(clock) = ops.get("hmvc").and_then(|operands| operands.first()) {
+        board.fifty_move_rule = fen::parse_halfmove_clock(clock)?;
+    }
+
+    Ok((board, ops))
+}
+
+/// Parse operations, each consisting of an opcode and operands followed by
+/// a semicolon
+///
+/// Operands within double quotes may contain spaces and semicolons.
+fn parse_operations(s: &str) -> Result<EpdOps, String> {
+    let mut ops = EpdOps::default();
+    let mut tokens = Vec::<String>::new();
+    let mut token = String::new();
+    let mut quoted = false;
+
+    let flush = |token: &mut String, tokens: &mut Vec<String>| {
+        if !token.is_empty() {
+            tokens.push(std::mem::take(token));
+        }
+    };
+
+    for c in s.chars() {
+        match c {
+            '"' if quoted => {
+                tokens.push(std::mem::take(&mut token));
+                quoted = false;
+            }
+            '"' => {
+                flush(&mut token, &mut tokens);
+                quoted = true;
+            }
+            ';' if !quoted => {
+                flush(&mut token, &mut tokens);
+                if tokens.is_empty() {
+                    return Err(String::from("EPD: Missing opcode"));
+                }
+
+                let opcode = tokens.remove(0);
+                ops.operations.push((opcode, std::mem::take(&mut tokens)));
+            }
+            c if c.is_whitespace() && !quoted => {
+                flush(&mut token, &mut tokens);
+            }
+            c => token.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(String::from("EPD: Unterminated string operand"));
+    }
+
+    flush(&mut token, &mut tokens);
+    if !tokens.is_empty() {
+        return Err(format!("EPD: Operation '{}' must end with ';'", s.trim()));
+    }
+
+    Ok(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations() {
+        let ops =
+            parse_operations(r#" bm Qg6 Rf8+; id "WAC 001; test"; c0 "";"#)
+                .unwrap();
+
+        assert_eq!(ops.best_moves(), ["Qg6", "Rf8+"]);
+        assert!(ops.avoid_moves().is_empty());
+        assert_eq!(ops.id(), Some("WAC 001; test"));
+        assert_eq!(ops.get("c0"), Some([String::new()].as_slice()));
+        assert_eq!(ops.iter().count(), 3);
+
+        assert!(parse_operations("").unwrap().iter().next().is_none());
+        assert!(parse_operations("bm Qg6").is_err());
+        assert!(parse_operations("id \"WAC;").is_err());
+        assert!(parse_operations("bm Qg6; ;").is_err());
+    }
+
+    #[test]
+    fn position() {
+        let (board, ops) =
+            parse("4k3/8/8/8/8/8/8/4K3 b - - hmvc 12; fmvn 40;").unwrap();
+        assert_eq!(board.fifty_move_rule, 12);
+        assert_eq!(ops.get("fmvn"), Some(["40".to_string()].as_slice()));
+
+        assert!(parse("4k3/8/8/8/8/8/8/4K3 b -").is_err());
+        assert!(parse("4k3/8/8/8/8/8/8/4K3 b - - hmvc x;").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use super::board_map::BoardMap;
+use super::gamestate::Gamestate;
+use super::movement::{self, PieceMove};
+use super::side::Side;
+use super::Game;
+use chess_notation_parser::Piece;
+
+/// Score of a won game
+pub const VICTORY_SCORE: i32 = 100_000;
+
+/// Centipawns awarded for every turn a player can make
+const MOBILITY_WEIGHT: i32 = 5;
+
+/// Evaluate the position in centipawns from White's point of view
+///
+/// Score is a sum of material on the board and a small mobility bonus.
+/// Mobility of the player on turn is the number of available turns, while
+/// the opponent's mobility is only estimated since their turns are not
+/// calculated.
+pub fn evaluate(game: &Game) -> i32 {
+    match game.gamestate() {
+        Gamestate::Ongoing => (),
+        Gamestate::Victory(winner) | Gamestate::Checkmate(winner) => {
+            return match winner == Side::White.to_string() {
+                true => VICTORY_SCORE,
+                _ => -VICTORY_SCORE,
+            }
+        }
+        // Any kind of draw
+        _ => return 0,
+    }
+
+    let map = &game.board.map;
+    let side = game.board.active_player;
+
+    let material: i32 = map
+        .into_iter()
+        .map(|(_, (piece, s))| side_sign(s) * piece_value(piece))
+        .sum();
+
+    let mobility = game.available_turns_raw().len() as i32
+        - estimate_mobility(map, side.opponent());
+
+    material + side_sign(side) * MOBILITY_WEIGHT * mobility
+}
+
+/// Value of a piece in centipawns
+pub fn piece_value(piece: Piece) -> i32 {
+    match piece {
+        Piece::Pawn => 100,
+        Piece::Knight => 320,
+        Piece::Bishop => 330,
+        Piece::Rook => 500,
+        Piece::Queen => 900,
+        // King is never captured, so it's worthless material wise
+        Piece::King => 0,
+    }
+}
+
+/// Scores are positive for White and negative for Black
+pub fn side_sign(side: Side) -> i32 {
+    match side {
+        Side::White => 1,
+        Side::Black => -1,
+    }
+}
+
+/// Count squares to which pieces can move, ignoring the king safety
+fn estimate_mobility(map: &BoardMap, side: Side) -> i32 {
+    map.into_iter()
+        .filter(|(_, (_, s))| *s == side)
+        .map(|(square, (piece, _))| match piece {
+            Piece::Pawn => {
+                let forward = movement::possible_squares_for_src(
+                    map,
+                    square,
+                    side,
+                    PieceMove::PawnNormal,
+                )
+                .into_iter()
+                .filter(|dst| map.get(dst).is_none());
+
+                let captures = movement::possible_squares_for_src(
+                    map,
+                    square,
+                    side,
+                    PieceMove::PawnCapture,
+                )
+                .into_iter()
+                .filter(|dst| map.get(dst).is_some());
+
+                forward.count() + captures.count()
+            }
+            _ => movement::possible_squares_for_src(
+                map,
+                square,
+                side,
+                PieceMove::from(piece),
+            )
+            .len(),
+        })
+        .sum::<usize>() as i32
+}
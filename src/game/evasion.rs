@@ -0,0 +1,12 @@
+/// Way a legal turn gets the king out of check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckEvasionKind {
+    /// King moves away from the check, possibly capturing the checking piece
+    KingMove,
+
+    /// Checking piece is captured by a piece other than the king
+    Capture,
+
+    /// Piece steps in between the king and the checking piece
+    Block,
+}
@@ -0,0 +1,487 @@
+use super::board::{Board, DEFAULT_FIFTY_MOVE_LIMIT};
+use super::board_map::BoardMap;
+use super::builder;
+use super::castling_rights::{CastlingRights, StartingRights};
+use super::castlinginfo;
+use super::enpassant::Enpassant;
+use super::gamestate::Gamestate;
+use super::pocket::Pockets;
+use super::side::Side;
+use super::variant::GameVariant;
+use chess_notation_parser::{CastlingType, Piece, Square};
+use std::collections::BTreeMap;
+
+/// Validate all six fields of a FEN record
+///
+/// Example of the default setup:
+/// `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1`
+pub fn validate(fen: &str) -> Result<(), String> {
+    parse(fen).map(|_| ())
+}
+
+/// Create a board out of all six fields of a FEN record
+///
+/// Full move number is only validated since turn history starts empty.
+pub fn parse(fen: &str) -> Result<Board, String> {
+    let mut fields = fen.split_whitespace();
+    let mut next_field = |name: &str| {
+        fields
+            .next()
+            .ok_or_else(|| format!("FEN: Missing {} field", name))
+    };
+
+    let mut board = parse_position(
+        next_field("piece placement")?,
+        next_field("side to move")?,
+        next_field("castling")?,
+        next_field("en-passant")?,
+    )?;
+    board.fifty_move_rule =
+        parse_halfmove_clock(next_field("halfmove clock")?)?;
+    parse_fullmove_number(next_field("fullmove number")?)?;
+
+    if fields.next().is_some() {
+        return Err(String::from("FEN: Too many fields"));
+    }
+
+    Ok(board)
+}
+
+/// Create a board out of the four position fields shared by FEN and EPD
+pub fn parse_position(
+    placement: &str,
+    side: &str,
+    castling: &str,
+    enpassant: &str,
+) -> Result<Board, String> {
+    let map = parse_placement(placement)?;
+    let active_player = parse_side(side)?;
+    let castling_rights = parse_castling(castling, &map)?;
+    let enpassant = parse_enpassant(enpassant, &map, active_player)?;
+
+    let king = map
+        .into_iter()
+        .filter(|(_, (piece, _))| *piece == Piece::King)
+        .map(|(square, (_, side))| (side, square))
+        .collect();
+
+    let board = Board {
+        map,
+        active_player,
+        king,
+        enpassant,
+        castling_rights,
+        gamestate: Gamestate::Ongoing,
+        fifty_move_rule: 0,
+        fifty_move_limit: DEFAULT_FIFTY_MOVE_LIMIT,
+        detect_draws: true,
+        state_hashes: BTreeMap::new(),
+        prev_state_hashes: vec![],
+        variant: GameVariant::Standard,
+        pockets: Pockets::default(),
+        promoted: 0,
+    };
+
+    match builder::is_idle_king_safe(&board) {
+        true => Ok(board),
+        _ => Err(format!(
+            "FEN: {} king must not be in check while {} is on turn",
+            active_player.opponent(),
+            active_player
+        )),
+    }
+}
+
+/// Parse piece placement, e.g. `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR`
+fn parse_placement(placement: &str) -> Result<BoardMap, String> {
+    let mut map = BoardMap::new();
+    let mut ranks = 0;
+
+    // Ranks are listed from the 8th to the 1st, same as `BoardMap` indexes
+    for (rank_idx, rank) in placement.split('/').enumerate() {
+        if rank_idx >= 8 {
+            return Err(String::from("FEN: Too many ranks"));
+        }
+        ranks += 1;
+
+        let mut file_idx = 0;
+        for c in rank.chars() {
+            if let Some(empty) = c.to_digit(10) {
+                if empty == 0 || empty > 8 {
+                    return Err(format!(
+                        "FEN: Invalid empty square count {}",
+                        c
+                    ));
+                }
+
+                file_idx += empty as usize;
+                continue;
+            }
+
+            if file_idx >= 8 {
+                return Err(format!("FEN: Rank '{}' is too long", rank));
+            }
+
+            let square = Square::from((rank_idx * 8 + file_idx) as u8);
+            map.insert(square, parse_figure(c)?);
+            file_idx += 1;
+        }
+
+        if file_idx != 8 {
+            return Err(format!("FEN: Rank '{}' must cover 8 files", rank));
+        }
+    }
+
+    if ranks != 8 {
+        return Err(String::from("FEN: Piece placement must have 8 ranks"));
+    }
+
+    validate_placement(&map)?;
+    Ok(map)
+}
+
+/// Parse FEN piece letter, uppercase for White and lowercase for Black
+fn parse_figure(c: char) -> Result<(Piece, Side), String> {
+    let side = match c.is_ascii_uppercase() {
+        true => Side::White,
+        false => Side::Black,
+    };
+
+    let piece = match c.to_ascii_uppercase() {
+        'P' => Piece::Pawn,
+        'N' => Piece::Knight,
+        'B' => Piece::Bishop,
+        'R' => Piece::Rook,
+        'Q' => Piece::Queen,
+        'K' => Piece::King,
+        _ => return Err(format!("FEN: Unknown piece '{}'", c)),
+    };
+
+    Ok((piece, side))
+}
+
+/// Export the board into all six fields of a FEN record
+///
+/// Full move number is not tracked by the board, so it's passed separately.
+pub fn export(board: &Board, fullmove: usize) -> String {
+    let side = match board.active_player {
+        Side::White => 'w',
+        Side::Black => 'b',
+    };
+
+    let castling: String = [
+        ((Side::White, CastlingType::Short), 'K'),
+        ((Side::White, CastlingType::Long), 'Q'),
+        ((Side::Black, CastlingType::Short), 'k'),
+        ((Side::Black, CastlingType::Long), 'q'),
+    ]
+    .into_iter()
+    .filter(|(castling, _)| board.castling_rights.contains(castling))
+    .map(|(_, c)| c)
+    .collect();
+
+    let enpassant = board
+        .enpassant
+        .map(|enpassant| enpassant.capture_pos.to_string());
+
+    format!(
+        "{} {} {} {} {} {}",
+        placement(&board.map),
+        side,
+        match castling.is_empty() {
+            true => "-",
+            _ => &castling,
+        },
+        enpassant.as_deref().unwrap_or("-"),
+        board.fifty_move_rule,
+        fullmove
+    )
+}
+
+/// Export piece placement, the first field of a FEN record
+pub fn placement(map: &BoardMap) -> String {
+    let mut s = String::with_capacity(72);
+
+    // Indexes go from the 8th rank to the 1st one, same as in FEN
+    for rank_idx in 0..8u8 {
+        if rank_idx > 0 {
+            s.push('/');
+        }
+
+        let mut empty = 0;
+        for file_idx in 0..8u8 {
+            match map.get(&Square::from(rank_idx * 8 + file_idx)) {
+                Some(figure) => {
+                    if empty > 0 {
+                        s.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    s.push(figure_char(figure));
+                }
+                None => empty += 1,
+            }
+        }
+
+        if empty > 0 {
+            s.push_str(&empty.to_string());
+        }
+    }
+
+    s
+}
+
+/// Get FEN piece letter, uppercase for White and lowercase for Black
+pub fn figure_char((piece, side): (Piece, Side)) -> char {
+    let c = match piece {
+        Piece::Pawn => 'P',
+        Piece::Knight => 'N',
+        Piece::Bishop => 'B',
+        Piece::Rook => 'R',
+        Piece::Queen => 'Q',
+        Piece::King => 'K',
+    };
+
+    match side {
+        Side::White => c,
+        Side::Black => c.to_ascii_lowercase(),
+    }
+}
+
+/// Check that placement contains exactly two kings and no misplaced pawns
+fn validate_placement(map: &BoardMap) -> Result<(), String> {
+    for side in [Side::White, Side::Black] {
+        let kings = map
+            .into_iter()
+            .filter(|(_, figure)| *figure == (Piece::King, side))
+            .count();
+
+        if kings != 1 {
+            return Err(format!("FEN: {} must have exactly one king", side));
+        }
+    }
+
+    let misplaced_pawn = map.into_iter().find(|(square, (piece, _))| {
+        *piece == Piece::Pawn && matches!(square.get_rank_char(), '1' | '8')
+    });
+
+    match misplaced_pawn {
+        Some((square, _)) => Err(format!("FEN: Pawn on the square {}", square)),
+        None => Ok(()),
+    }
+}
+
+/// Parse side to move
+fn parse_side(side: &str) -> Result<Side, String> {
+    match side {
+        "w" => Ok(Side::White),
+        "b" => Ok(Side::Black),
+        _ => Err(format!("FEN: Invalid side to move '{}'", side)),
+    }
+}
+
+/// Parse castling availability, e.g. `KQkq` or `-`
+///
+/// King and rook must be placed on their starting squares for every castling.
+fn parse_castling(
+    castling: &str,
+    map: &BoardMap,
+) -> Result<CastlingRights, String> {
+    let mut rights = CastlingRights::new(StartingRights::None);
+
+    if castling == "-" {
+        return Ok(rights);
+    }
+
+    for c in castling.chars() {
+        let side_castling = match c {
+            'K' => (Side::White, CastlingType::Short),
+            'Q' => (Side::White, CastlingType::Long),
+            'k' => (Side::Black, CastlingType::Short),
+            'q' => (Side::Black, CastlingType::Long),
+            _ => return Err(format!("FEN: Invalid castling '{}'", castling)),
+        };
+
+        // `insert` returns true for already present rights
+        if rights.insert(side_castling) {
+            return Err(format!("FEN: Repeated castling '{}'", c));
+        }
+
+        let (side, castling_type) = side_castling;
+        let king = castlinginfo::get_path_king(side, castling_type).src;
+        let rook = castlinginfo::get_path_rook(side, castling_type).src;
+
+        if map.get(&king) != Some((Piece::King, side))
+            || map.get(&rook) != Some((Piece::Rook, side))
+        {
+            return Err(format!(
+                "FEN: Castling '{}' requires king and rook on {} and {}",
+                c, king, rook
+            ));
+        }
+    }
+
+    Ok(rights)
+}
+
+/// Parse en-passant target square, e.g. `e3` or `-`
+///
+/// Pawn which made the two-square move must be placed in front of it.
+fn parse_enpassant(
+    enpassant: &str,
+    map: &BoardMap,
+    active_player: Side,
+) -> Result<Option<Enpassant>, String> {
+    if enpassant == "-" {
+        return Ok(None);
+    }
+
+    let err = || format!("FEN: Invalid en-passant square '{}'", enpassant);
+    let capture_pos = Square::try_from(enpassant).map_err(|_| err())?;
+
+    // Opponent has made the two-square pawn move
+    let (rank, y_dir) = match active_player {
+        Side::White => ('6', -1),
+        Side::Black => ('3', 1),
+    };
+
+    if capture_pos.get_rank_char() != rank || map.get(&capture_pos).is_some() {
+        return Err(err());
+    }
+
+    let pawn_src = capture_pos.get_relative_neighbor(0, y_dir).unwrap();
+    if map.get(&pawn_src) != Some((Piece::Pawn, active_player.opponent())) {
+        return Err(format!(
+            "FEN: En-passant square '{}' has no pawn in front of it",
+            enpassant
+        ));
+    }
+
+    Ok(Some(Enpassant {
+        pawn_src,
+        capture_pos,
+    }))
+}
+
+/// Parse number of half moves since the last capture or pawn move
+pub fn parse_halfmove_clock(clock: &str) -> Result<u8, String> {
+    clock
+        .parse::<u8>()
+        .map_err(|_| format!("FEN: Invalid halfmove clock '{}'", clock))
+}
+
+/// Parse full move number which starts at 1
+fn parse_fullmove_number(number: &str) -> Result<u16, String> {
+    match number.parse::<u16>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("FEN: Invalid fullmove number '{}'", number)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: &str =
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    #[test]
+    fn valid() {
+        assert!(validate(START).is_ok());
+        assert!(validate("4k3/8/8/8/8/8/8/4K3 b - - 12 40").is_ok());
+        assert!(validate(
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3"
+        )
+        .is_ok());
+
+        let map = parse_placement("4k3/8/8/8/4P3/8/8/4K3").unwrap();
+        assert_eq!(map.len(), 3);
+        assert!(map.get(&Square::E4) == Some((Piece::Pawn, Side::White)));
+
+        let enpassant = parse_enpassant("e3", &map, Side::Black).unwrap();
+        assert_eq!(enpassant.unwrap().pawn_src.to_string(), "e4");
+
+        let board = parse("4k3/8/8/8/4P3/8/8/4K3 b - e3 7 20").unwrap();
+        assert!(board.active_player == Side::Black);
+        assert_eq!(board.fifty_move_rule, 7);
+        assert_eq!(board.king[&Side::White].to_string(), "e1");
+        assert_eq!(board.king[&Side::Black].to_string(), "e8");
+        assert!(board.enpassant.is_some());
+    }
+
+    #[test]
+    fn export_record() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w Kq d6 0 1",
+            "4k3/8/8/8/8/8/8/R3K3 b Q - 12 1",
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        ] {
+            assert_eq!(export(&parse(fen).unwrap(), 1), fen);
+        }
+    }
+
+    #[test]
+    fn export_placement() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR",
+            "4k3/8/8/8/4P3/8/8/4K3",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R",
+        ] {
+            assert_eq!(placement(&parse_placement(fen).unwrap()), fen);
+        }
+    }
+
+    #[test]
+    fn invalid_fields() {
+        let invalid = [
+            // Missing and extra fields
+            "",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 1",
+            // Side to move
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1",
+            // Castling
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KKkq - 0 1",
+            "rnbqkbn1/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            // En-passant
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e9 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e6 0 1",
+            // Clocks
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0",
+        ];
+
+        for fen in invalid {
+            assert!(validate(fen).is_err(), "FEN: {}", fen);
+        }
+    }
+
+    #[test]
+    fn invalid_placement() {
+        let invalid = [
+            // Wrong number of ranks
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w - - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            // Ranks not summing to 8 files
+            "rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            "rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            "rnbqkbnrr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            // Unknown piece
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w - - 0 1",
+            // Kings
+            "rnbq1bnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            "rnbqkbnr/pppppppp/8/8/4K3/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            // Pawn on the last rank
+            "rnbqkbnP/pppppppp/8/8/8/8/PPPPPPP1/RNBQKBNR w - - 0 1",
+            // King of the player not on turn is in check
+            "4k3/8/8/8/8/8/8/r3K3 b - - 0 1",
+            "4k3/4R3/8/8/8/8/8/4K3 w - - 0 1",
+        ];
+
+        for fen in invalid {
+            assert!(validate(fen).is_err(), "FEN: {}", fen);
+        }
+    }
+}
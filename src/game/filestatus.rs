@@ -0,0 +1,15 @@
+/// Pawn presence on a file of the board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    /// No pawns on the file
+    Open,
+
+    /// Only Black's pawns on the file, so it's half-open for White
+    HalfOpenWhite,
+
+    /// Only White's pawns on the file, so it's half-open for Black
+    HalfOpenBlack,
+
+    /// Pawns of both players on the file
+    Closed,
+}
@@ -8,6 +8,9 @@ pub enum GameError {
     /// Origin square not found for moving piece
     MovingPieceNotFound,
 
+    /// Only the opponent's piece can make the turn, turn played out of order
+    NotYourPiece,
+
     /// Unexpected king state for the turn
     KingIsSafe,
 
@@ -33,6 +36,9 @@ pub enum GameError {
     /// destination square
     NoCapturePiece,
 
+    /// King is never captured, position is broken if a turn attempts it
+    IllegalKingCapture,
+
     /// Game over
     GameOver(Gamestate),
 
@@ -56,8 +62,31 @@ pub enum GameError {
     /// Turn notation is not correct
     ParsingTurnFailed,
 
+    /// Move in the UCI format doesn't match any legal turn
+    IllegalUciMove(String),
+
+    /// Pieces can be dropped only onto empty squares, pawns not on the first
+    /// or the last rank
+    InvalidDrop,
+
+    /// Dropped piece must be taken from the pocket of the player on turn
+    PieceNotInPocket,
+
     /// Undo unavailable
     UndoNotAvailable,
+
+    /// No turn was taken back from the current position
+    RedoNotAvailable,
+
+    /// FEN or EPD record is not correct
+    ParsingFenFailed(String),
+
+    /// Encoded board doesn't describe a valid setup
+    InvalidBoardBytes(&'static str),
+
+    /// Move bytes describe only the games from the default setup in the
+    /// standard variant
+    UnsupportedMoveBytesSetup,
 }
 
 impl error::Error for GameError {}
@@ -74,6 +103,9 @@ impl GameError {
             Self::MovingPieceNotFound => {
                 "Source square not found for the moving piece".to_string()
             }
+            Self::NotYourPiece => {
+                "Invalid turn: Piece belongs to the opponent".to_string()
+            }
             Self::NoCapturePiece => {
                 "Nothing to capture, dst square empty".to_string()
             }
@@ -90,6 +122,9 @@ impl GameError {
             Self::CaptureAlly => {
                 "Capturing ally pieces not allowed".to_string()
             }
+            Self::IllegalKingCapture => {
+                "Invalid turn: King cannot be captured".to_string()
+            }
             Self::CastlingUnavailable => "Castling not available".to_string(),
             Self::GameOver(gamestate) => format!("Game over: {}", gamestate),
             Self::CastlingUnderCheck => {
@@ -109,7 +144,20 @@ impl GameError {
                 "Pawns move diagonally only by capture".to_string()
             }
             Self::ParsingTurnFailed => "Parsing turn failed".to_string(),
+            Self::IllegalUciMove(uci) => format!("Illegal UCI move '{}'", uci),
+            Self::InvalidDrop => {
+                "Piece cannot be dropped onto this square".to_string()
+            }
+            Self::PieceNotInPocket => {
+                "Dropped piece not found in the pocket".to_string()
+            }
             Self::UndoNotAvailable => "Undo not available".to_string(),
+            Self::RedoNotAvailable => "Redo not available".to_string(),
+            Self::ParsingFenFailed(err) => err.to_string(),
+            Self::InvalidBoardBytes(err) => err.to_string(),
+            Self::UnsupportedMoveBytesSetup => {
+                "Move bytes support only the default setup".to_string()
+            }
         }
     }
 }
@@ -122,6 +170,7 @@ mod tests {
     fn print() {
         let errors = [
             GameError::MovingPieceNotFound,
+            GameError::NotYourPiece,
             GameError::KingIsSafe,
             GameError::KingIsInCheck,
             GameError::KingIsInCheckmate,
@@ -130,13 +179,21 @@ mod tests {
             GameError::CaptureNotSet,
             GameError::CaptureAlly,
             GameError::NoCapturePiece,
+            GameError::IllegalKingCapture,
             GameError::GameOver(Gamestate::Ongoing),
             GameError::CastlingUnderCheck,
             GameError::CastlingUnavailable,
             GameError::CastlingSquaresNotEmpty,
             GameError::InvalidPawnMovement,
             GameError::ParsingTurnFailed,
+            GameError::IllegalUciMove(String::from("e2e5")),
+            GameError::InvalidDrop,
+            GameError::PieceNotInPocket,
             GameError::UndoNotAvailable,
+            GameError::RedoNotAvailable,
+            GameError::ParsingFenFailed(String::from("FEN: Too many fields")),
+            GameError::InvalidBoardBytes("Game needs to have two kings"),
+            GameError::UnsupportedMoveBytesSetup,
         ];
 
         errors.iter().for_each(|err| {
@@ -1,3 +1,4 @@
+use super::side::Side;
 use std::fmt;
 
 /// Board game state
@@ -21,6 +22,9 @@ pub enum Gamestate {
 
     /// Victory and name of the winner
     Victory(String),
+
+    /// Victory by checkmate and name of the winner
+    Checkmate(String),
 }
 
 impl fmt::Display for Gamestate {
@@ -30,6 +34,7 @@ impl fmt::Display for Gamestate {
             "{}",
             match self {
                 Self::Victory(side) => format!("{} won", side),
+                Self::Checkmate(side) => format!("{} won by checkmate", side),
                 Self::Ongoing => "Ongoing".to_owned(),
                 Self::Stalemate => "Stalemate".to_owned(),
                 Self::DrawFiftyMoveRule => "Draw by fifty move rule".to_owned(),
@@ -42,6 +47,22 @@ impl fmt::Display for Gamestate {
     }
 }
 
+impl Gamestate {
+    /// Get game result written as a PGN result token
+    pub fn result_token(&self) -> &'static str {
+        match self {
+            Self::Ongoing => "*",
+            Self::Victory(winner) | Self::Checkmate(winner) => {
+                match *winner == Side::White.to_string() {
+                    true => "1-0",
+                    _ => "0-1",
+                }
+            }
+            _ => "1/2-1/2",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,5 +91,24 @@ mod tests {
             Gamestate::Victory("Black".to_string()).to_string().as_str(),
             "Black won"
         );
+        assert_eq!(
+            Gamestate::Checkmate("White".to_string()).to_string(),
+            "White won by checkmate"
+        );
+    }
+
+    #[test]
+    fn result_token() {
+        assert_eq!(Gamestate::Ongoing.result_token(), "*");
+        assert_eq!(Gamestate::Stalemate.result_token(), "1/2-1/2");
+        assert_eq!(Gamestate::DrawFiftyMoveRule.result_token(), "1/2-1/2");
+        assert_eq!(
+            Gamestate::Victory("White".to_string()).result_token(),
+            "1-0"
+        );
+        assert_eq!(
+            Gamestate::Checkmate("Black".to_string()).result_token(),
+            "0-1"
+        );
     }
 }
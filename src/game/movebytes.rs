@@ -0,0 +1,133 @@
+use super::castlinginfo;
+use super::game_error::GameError;
+use super::play;
+use super::rawturn::RawTurn;
+use super::variant::GameVariant;
+use super::Game;
+use chess_notation_parser::{Piece, Square, Turn};
+
+/// FEN record of the only setup the encoded turns are replayed on
+const NORMAL_SETUP_FEN: &str =
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Bits used by the source and destination squares
+const SQUARE_BITS: u16 = 6;
+const SQUARE_MASK: u16 = (1 << SQUARE_BITS) - 1;
+
+/// Encoded null turn, real turns never use the highest bit
+const NULL_PACKED: u16 = u16::MAX;
+
+/// Encode every played turn into 2 bytes (little endian)
+///
+/// Bits `0..6` hold the source square, bits `6..12` the destination square
+/// and bits `12..15` the promotion piece. Castling is encoded as the king's
+/// move. Null turns have all the bits set.
+///
+/// Neither the setup nor the variant is encoded, so only the games started
+/// from the default setup in the standard variant are supported.
+pub fn encode(game: &Game) -> Result<Vec<u8>, GameError> {
+    if game.setup_fen != NORMAL_SETUP_FEN
+        || game.board.variant != GameVariant::Standard
+    {
+        return Err(GameError::UnsupportedMoveBytesSetup);
+    }
+
+    // Find out who played the first turn in the history
+    let mut side = game.board.active_player;
+    if game.history.len() % 2 == 1 {
+        side.switch_side();
+    }
+
+    let mut bytes = Vec::with_capacity(game.history.len() * 2);
+    for state in game.history.iter() {
+        let turn = state.get_turn();
+        if turn == play::NULL_TURN {
+            bytes.extend_from_slice(&NULL_PACKED.to_le_bytes());
+            side.switch_side();
+            continue;
+        }
+
+        let (src, dst, promotion) = match Turn::try_from(turn) {
+            Ok(Turn::Castling(castling)) => {
+                let path = castlinginfo::get_path_king(side, castling.r#type);
+                (path.src, path.dst, None)
+            }
+            Ok(Turn::Move(turn)) => match state.moving_piece_src {
+                Some(src) => (src, turn.dst, turn.promotion),
+                None => return Err(GameError::ParsingTurnFailed),
+            },
+            Err(_) => return Err(GameError::ParsingTurnFailed),
+        };
+
+        bytes.extend_from_slice(&pack(src, dst, promotion).to_le_bytes());
+        side.switch_side();
+    }
+
+    Ok(bytes)
+}
+
+/// Replay turns encoded with `encode` on the `game`
+pub fn decode(game: &mut Game, bytes: &[u8]) -> Result<(), GameError> {
+    let chunks = bytes.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err(GameError::ParsingTurnFailed);
+    }
+
+    for chunk in chunks {
+        let packed = u16::from_le_bytes([chunk[0], chunk[1]]);
+        if packed == NULL_PACKED {
+            game.play_turn_strict(play::NULL_TURN)?;
+            continue;
+        }
+
+        let turn = game
+            .available_turns_raw()
+            .iter()
+            .find(|turn| pack_raw(turn) == packed)
+            .map(|turn| turn.get_turn())
+            .ok_or(GameError::ParsingTurnFailed)?;
+
+        game.play_turn(&turn)?;
+    }
+
+    Ok(())
+}
+
+/// Pack the available turn the same way as the played one
+fn pack_raw(turn: &RawTurn) -> u16 {
+    pack(turn.src, turn.dst, turn.promotion())
+}
+
+/// Pack squares and the promotion piece of the turn into 15 bits
+fn pack(src: Square, dst: Square, promotion: Option<Piece>) -> u16 {
+    let promotion: u16 = match promotion {
+        None => 0,
+        Some(Piece::Knight) => 1,
+        Some(Piece::Bishop) => 2,
+        Some(Piece::Rook) => 3,
+        Some(Piece::Queen) => 4,
+        Some(Piece::Pawn | Piece::King) => {
+            unreachable!("Pawn is never promoted to a pawn or a king")
+        }
+    };
+
+    (src as u16 & SQUARE_MASK)
+        | (dst as u16 & SQUARE_MASK) << SQUARE_BITS
+        | promotion << (2 * SQUARE_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        assert_eq!(pack(Square::A8, Square::A8, None), 0);
+        assert_eq!(pack(Square::H1, Square::A8, None), 63);
+        assert_eq!(pack(Square::A8, Square::H1, None), 63 << 6);
+        assert_eq!(
+            pack(Square::E7, Square::E8, Some(Piece::Queen)),
+            12 | 4 << 6 | 4 << 12
+        );
+    }
+}
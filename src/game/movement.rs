@@ -209,6 +209,85 @@ pub fn possible_squares_for_src(
     dst_squares
 }
 
+/// Get list of squares with ally pieces which are defended by the piece
+///
+/// # Arguments
+///
+/// * `map` - board map
+/// * `src` - Square of the defending piece
+/// * `side` - Color of the defending piece
+/// * `piece_move` - Defending piece encapsulated in `PieceMove`
+pub fn defended_squares(
+    map: &BoardMap,
+    src: Square,
+    side: Side,
+    piece_move: PieceMove,
+) -> Vec<Square> {
+    get_move_pattern(src, side, piece_move, PawnDir::Forward)
+        .into_iter()
+        // Only the first piece in each direction is defended
+        .filter_map(|mut dep_move| dep_move.find(|s| map.get(s).is_some()))
+        .filter(|square| matches!(map.get(square), Some((_, s)) if s == side))
+        .collect()
+}
+
+/// Get list of squares with `side` pieces which attack the `dst` square
+///
+/// Pinned pieces are included as well, since they still attack the square.
+///
+/// # Arguments
+///
+/// * `map` - board map
+/// * `dst` - Attacked square
+/// * `side` - Color of the attacking pieces
+pub fn attackers(map: &BoardMap, dst: Square, side: Side) -> Vec<Square> {
+    [
+        PieceMove::King,
+        PieceMove::Queen,
+        PieceMove::Bishop,
+        PieceMove::Knight,
+        PieceMove::Rook,
+        PieceMove::PawnCapture,
+    ]
+    .into_iter()
+    .flat_map(|piece_move| possible_squares_for_dst(map, dst, side, piece_move))
+    .collect()
+}
+
+/// Find exact source square of the `side` piece moving to the `dst` square
+///
+/// If none of the `side` pieces can make the turn, but the opponent's piece
+/// can, the turn was played out of order and `GameError::NotYourPiece` is
+/// returned instead of `GameError::MovingPieceNotFound`.
+///
+/// # Arguments
+///
+/// * `map` - board map
+/// * `dst` - Destination square for the moving piece
+/// * `side` - Color of the moving piece
+/// * `piece_move` - Moving piece which is moving encapsulated in `PieceMove`
+/// * `turn_src` - Info about originating source square provided via turn
+///   notation
+pub fn find_src(
+    map: &BoardMap,
+    dst: Square,
+    side: Side,
+    piece_move: PieceMove,
+    turn_src: Option<Vec<Square>>,
+) -> Result<Square, GameError> {
+    let possible_src = possible_squares_for_dst(map, dst, side, piece_move);
+    if !possible_src.is_empty() {
+        return get_exact_src(possible_src, turn_src);
+    }
+
+    let opponent_src =
+        possible_squares_for_dst(map, dst, side.opponent(), piece_move);
+    match get_exact_src(opponent_src, turn_src) {
+        Ok(_) => Err(GameError::NotYourPiece),
+        Err(e) => Err(e),
+    }
+}
+
 /// Find exact source square by comparing possible squares with info about
 /// the source squares that was received from the annotated turn
 ///
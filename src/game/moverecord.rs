@@ -0,0 +1,39 @@
+use super::state::State;
+use chess_notation_parser::{Piece, Square};
+
+/// Record of a played turn from the turn history
+#[derive(Clone)]
+pub struct MoveRecord {
+    /// Played turn written in chess notation format
+    pub turn: String,
+
+    /// Source square of the moving piece
+    ///
+    /// It's `None` for castling turns.
+    pub src: Option<Square>,
+
+    /// Captured piece
+    pub captured: Option<Piece>,
+
+    /// Turn gave a check or a checkmate
+    pub was_check: bool,
+
+    /// Turn captured a piece
+    pub was_capture: bool,
+
+    /// Turn was a castling
+    pub was_castle: bool,
+}
+
+impl From<&State> for MoveRecord {
+    fn from(state: &State) -> Self {
+        Self {
+            turn: String::from(state.get_turn()),
+            src: state.moving_piece_src,
+            captured: state.captured.map(|(_, (piece, _))| piece),
+            was_check: state.was_check,
+            was_capture: state.captured.is_some(),
+            was_castle: state.was_castle,
+        }
+    }
+}
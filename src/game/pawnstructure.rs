@@ -0,0 +1,47 @@
+use super::board_map::{square_to_coords, BoardMap};
+use super::side::Side;
+use chess_notation_parser::{Piece, Square};
+
+/// Weaknesses in the pawn structure of one side
+#[derive(Clone, Default)]
+pub struct PawnStructure {
+    /// Pawns sharing their file with another pawn of the same side
+    pub doubled: Vec<Square>,
+
+    /// Pawns without any pawn of the same side on the neighboring files
+    pub isolated: Vec<Square>,
+}
+
+impl PawnStructure {
+    /// Find weak pawns of the `side`
+    pub fn new(map: &BoardMap, side: Side) -> Self {
+        let pawns: Vec<(Square, u8)> = map
+            .into_iter()
+            .filter(|(_, figure)| *figure == (Piece::Pawn, side))
+            .map(|(square, _)| (square, square_to_coords(square).0))
+            .collect();
+
+        let mut per_file = [0u8; 8];
+        pawns
+            .iter()
+            .for_each(|(_, file)| per_file[*file as usize] += 1);
+
+        let doubled = pawns
+            .iter()
+            .filter(|(_, file)| per_file[*file as usize] > 1)
+            .map(|(square, _)| *square)
+            .collect();
+
+        let isolated = pawns
+            .iter()
+            .filter(|(_, file)| {
+                let left = file.checked_sub(1).map(|f| per_file[f as usize]);
+                let right = per_file.get(*file as usize + 1).copied();
+                left.unwrap_or(0) == 0 && right.unwrap_or(0) == 0
+            })
+            .map(|(square, _)| *square)
+            .collect();
+
+        Self { doubled, isolated }
+    }
+}
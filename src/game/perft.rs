@@ -0,0 +1,81 @@
+use super::gamestate::Gamestate;
+use super::search;
+use super::Game;
+
+/// Count all leaf nodes of the turn tree up to the given `depth`
+///
+/// Turns are played and undone on the `game`, so the game is left in its
+/// original state once the counting is finished. Branches end early once the
+/// game is over.
+///
+/// # Arguments
+///
+/// * `game` - Game whose current position is the root of the tree
+/// * `depth` - Number of half moves to count
+pub fn perft(game: &mut Game, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    if game.gamestate() != Gamestate::Ongoing {
+        return 0;
+    }
+
+    // Turns on the last level don't need to be played
+    if depth == 1 {
+        return game.available_turns_raw().len() as u64;
+    }
+
+    let mut nodes = 0;
+
+    for turn in search::collect_turns(game) {
+        game.play_turn_keep_redo(&turn)
+            .expect("Available turn failed");
+        nodes += perft(game, depth - 1);
+        game.undo_turn().expect("Undo of a played turn failed");
+    }
+
+    nodes
+}
+
+/// Count leaf nodes of the turn tree separately for every root turn
+///
+/// Sum of all counts equals the `perft` result for the same `depth`. No root
+/// turns are listed for `depth` 0 or once the game is over.
+pub fn perft_divide(game: &mut Game, depth: usize) -> Vec<(String, u64)> {
+    if depth == 0 || game.gamestate() != Gamestate::Ongoing {
+        return vec![];
+    }
+
+    search::collect_turns(game)
+        .into_iter()
+        .map(|turn| {
+            game.play_turn_keep_redo(&turn)
+                .expect("Available turn failed");
+            let nodes = perft(game, depth - 1);
+            game.undo_turn().expect("Undo of a played turn failed");
+            (turn, nodes)
+        })
+        .collect()
+}
+
+/// Same as `perft`, but every root turn is counted on its own thread with its
+/// own copy of the game
+#[cfg(feature = "rayon")]
+pub fn perft_parallel(game: &Game, depth: usize) -> u64 {
+    use rayon::prelude::*;
+
+    if depth <= 1 || game.gamestate() != Gamestate::Ongoing {
+        return perft(&mut game.clone(), depth);
+    }
+
+    search::collect_turns(game)
+        .into_par_iter()
+        .map(|turn| {
+            let mut game = game.clone();
+            game.play_turn_keep_redo(&turn)
+                .expect("Available turn failed");
+            perft(&mut game, depth - 1)
+        })
+        .sum()
+}
@@ -0,0 +1,138 @@
+use super::board::Board;
+use super::game_error::GameError;
+use super::play;
+use super::state::State;
+use super::variant::GameVariant;
+use chess_notation_parser::{Flag, Piece, Square};
+use std::fmt;
+
+/// Piece dropped from the pocket onto an empty square, e.g. `N@e5`
+///
+/// Drops are available only in the Crazyhouse variant. Pawn drops are
+/// written either as `P@e5` or `@e5`.
+#[derive(Clone, Copy)]
+pub struct PieceDrop {
+    /// Dropped piece
+    pub piece: Piece,
+
+    /// Destination square
+    pub dst: Square,
+
+    /// Check and checkmate flags
+    pub flags: u8,
+}
+
+impl TryFrom<&str> for PieceDrop {
+    type Error = GameError;
+
+    fn try_from(turn: &str) -> Result<Self, Self::Error> {
+        let (piece, dst) =
+            turn.split_once('@').ok_or(GameError::ParsingTurnFailed)?;
+
+        let piece = match piece {
+            "" => Piece::Pawn,
+            "K" => return Err(GameError::ParsingTurnFailed),
+            _ => Piece::try_from(piece)
+                .map_err(|_| GameError::ParsingTurnFailed)?,
+        };
+
+        let (dst, flags) = match dst.strip_suffix('#') {
+            Some(dst) => (dst, Flag::CHECKMATE),
+            None => match dst.strip_suffix('+') {
+                Some(dst) => (dst, Flag::CHECK),
+                None => (dst, Flag::NONE),
+            },
+        };
+
+        let dst =
+            Square::try_from(dst).map_err(|_| GameError::ParsingTurnFailed)?;
+
+        Ok(Self { piece, dst, flags })
+    }
+}
+
+impl fmt::Display for PieceDrop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let piece = match self.piece {
+            Piece::Pawn => 'P',
+            Piece::Knight => 'N',
+            Piece::Bishop => 'B',
+            Piece::Rook => 'R',
+            Piece::Queen => 'Q',
+            Piece::King => 'K',
+        };
+
+        let flag = match self.flags {
+            flags if flags & Flag::CHECKMATE != 0 => "#",
+            flags if flags & Flag::CHECK != 0 => "+",
+            _ => "",
+        };
+
+        write!(f, "{}@{}{}", piece, self.dst, flag)
+    }
+}
+
+/// Check whether the `piece` may be dropped onto the `dst` square
+///
+/// Square must be empty and pawns cannot be dropped on the first or the last
+/// rank.
+pub fn can_drop_on(board: &Board, piece: Piece, dst: Square) -> bool {
+    board.map.get(&dst).is_none()
+        && (piece != Piece::Pawn || !matches!(dst.get_rank_char(), '1' | '8'))
+}
+
+/// Play the drop and take the piece out of the pocket
+pub fn play_drop(
+    board: &mut Board,
+    turn: &PieceDrop,
+) -> Result<State, GameError> {
+    if board.variant != GameVariant::Crazyhouse {
+        return Err(GameError::ParsingTurnFailed);
+    }
+
+    let side = board.active_player;
+    if !can_drop_on(board, turn.piece, turn.dst) {
+        return Err(GameError::InvalidDrop);
+    }
+
+    if !board.pockets.remove(side, turn.piece) {
+        return Err(GameError::PieceNotInPocket);
+    }
+
+    let state = State::new(board, turn.to_string());
+    board.map.insert(turn.dst, (turn.piece, side));
+
+    if let Err(e) = play::verify_check_checkmate(board, turn.flags) {
+        board.undo(state);
+        board.pockets.add(side, turn.piece);
+        return Err(e);
+    }
+
+    // Clear en-passant state for drops
+    board.enpassant = None;
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_print() {
+        for (turn, printed) in [
+            ("N@e5", "N@e5"),
+            ("@e4", "P@e4"),
+            ("P@e4+", "P@e4+"),
+            ("Q@h7#", "Q@h7#"),
+        ] {
+            let drop = PieceDrop::try_from(turn).unwrap();
+            assert_eq!(drop.to_string(), printed);
+        }
+
+        assert!(PieceDrop::try_from("N@e5").unwrap().piece == Piece::Knight);
+        for turn in ["Ne5", "K@e5", "N@", "N@e9", "X@e5"] {
+            assert!(PieceDrop::try_from(turn).is_err());
+        }
+    }
+}
@@ -1,21 +1,79 @@
+use super::board;
 use super::castlinginfo;
 use super::enpassant::Enpassant;
 use super::game_error::GameError;
 use super::gamestate::Gamestate;
 use super::king::{self, KingState};
 use super::movement::{self, PieceMove};
+use super::piecedrop::{self, PieceDrop};
 use super::side::Side;
 use super::simulation;
+use super::variant::GameVariant;
 use super::{Board, State};
 use chess_notation_parser::{Castling, CastlingType, Move, Turn};
 use chess_notation_parser::{Flag, FlagCheck, Piece, Square};
 
+/// Strip a leading move number, e.g. `12. Nf3` or `12...Nf6`
+///
+/// Turns without the move number are returned trimmed, but otherwise intact.
+pub fn strip_move_number(turn: &str) -> &str {
+    let turn = turn.trim();
+
+    let digits = turn
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(turn.len());
+    if digits == 0 {
+        return turn;
+    }
+
+    let rest = &turn[digits..];
+    match rest.strip_prefix("...").or_else(|| rest.strip_prefix('.')) {
+        Some(rest) => rest.trim_start(),
+        None => turn,
+    }
+}
+
+/// Null turn which only passes the turn to the opponent
+pub const NULL_TURN: &str = "--";
+
+/// Annotation glyphs which may follow a turn, e.g. `Nf3!?`
+///
+/// Two-character glyphs come first so they are stripped as a whole.
+const ANNOTATIONS: [&str; 6] = ["!!", "??", "!?", "?!", "!", "?"];
+
+/// Strip a trailing annotation glyph, e.g. `Qxf7#!!` becomes `Qxf7#`
+pub fn strip_annotation(turn: &str) -> &str {
+    ANNOTATIONS
+        .iter()
+        .find_map(|glyph| turn.strip_suffix(glyph))
+        .unwrap_or(turn)
+}
+
 /// Verify and play the turn and update the board state
+///
+/// Annotation glyphs are ignored, while check and checkmate flags are
+/// verified against the resulting position.
 pub fn next_turn(board: &mut Board, turn: &str) -> Result<State, GameError> {
     if board.gamestate != Gamestate::Ongoing {
         return Err(GameError::GameOver(board.gamestate.clone()));
     }
 
+    let turn = strip_annotation(turn);
+
+    if turn == NULL_TURN {
+        let state = play_null(board)?;
+        return Ok(finish_turn(board, state, false, false));
+    }
+
+    // Drops are written with the `@` sign, e.g. `N@e5`
+    if turn.contains('@') {
+        let turn = PieceDrop::try_from(turn)?;
+        let mut state = piecedrop::play_drop(board, &turn)?;
+        let checkmate = turn.flags & Flag::CHECKMATE != 0;
+        state.was_check = turn.flags & (Flag::CHECK | Flag::CHECKMATE) != 0;
+        return Ok(finish_turn(board, state, false, checkmate));
+    }
+
     let mut turn = match chess_notation_parser::Turn::try_from(turn) {
         Err(_) => return Err(GameError::ParsingTurnFailed),
         Ok(turn) => turn,
@@ -26,13 +84,56 @@ pub fn next_turn(board: &mut Board, turn: &str) -> Result<State, GameError> {
         Turn::Move(ref mut turn) => play_move(board, turn),
     };
 
-    if let Err(e) = prev_board_state {
-        return Err(e);
+    let mut prev_board_state = prev_board_state?;
+
+    // Flags were verified against the position, so they can be kept as is
+    prev_board_state.was_check = turn.is_check() || turn.is_checkmate();
+    prev_board_state.was_castle = matches!(turn, Turn::Castling(_));
+
+    // Board states before a capture or pawn movement cannot occur again
+    let irreversible = match turn {
+        Turn::Move(ref turn) => {
+            turn.who == Piece::Pawn || prev_board_state.captured.is_some()
+        }
+        Turn::Castling(_) => false,
+    };
+
+    Ok(finish_turn(
+        board,
+        prev_board_state,
+        irreversible,
+        turn.is_checkmate(),
+    ))
+}
+
+/// Update the board state which follows every played turn and switch the
+/// player on turn
+fn finish_turn(
+    board: &mut Board,
+    mut prev_board_state: State,
+    irreversible: bool,
+    checkmate: bool,
+) -> State {
+    if irreversible {
+        board.clear_state_hashes();
+        prev_board_state.state_hashes_cleared = true;
+    }
+
+    // Captured pieces go into the pocket of the capturing side
+    if board.variant == GameVariant::Crazyhouse {
+        if let Some(piece) = prev_board_state.pocketed_piece() {
+            board.pockets.add(board.active_player, piece);
+        }
     }
 
-    board.fifty_move_rule += 1;
-    // Fifty moves per player totals to hundred
-    if board.fifty_move_rule >= 100 {
+    // Counter keeps going when draws are not detected, and it's zero right
+    // after the capture or the pawn move, as in the FEN halfmove clock
+    board.fifty_move_rule = match irreversible {
+        true => 0,
+        _ => board.fifty_move_rule.saturating_add(1),
+    };
+    // Fifty moves per player totals to hundred by default
+    if board.detect_draws && board.fifty_move_rule >= board.fifty_move_limit {
         board.gamestate = Gamestate::DrawFiftyMoveRule;
     }
 
@@ -40,12 +141,30 @@ pub fn next_turn(board: &mut Board, turn: &str) -> Result<State, GameError> {
     // if all condtions are met. Otherwise, it returns the current gamestate.
     board.gamestate = board.hash_state_push();
 
-    if turn.is_checkmate() {
-        board.gamestate = Gamestate::Victory(board.active_player.to_string());
+    if checkmate {
+        board.gamestate = Gamestate::Checkmate(board.active_player.to_string());
     }
 
     board.active_player.switch_side();
-    Ok(prev_board_state.unwrap())
+    prev_board_state
+}
+
+/// Play the null turn
+///
+/// Player in check cannot pass the turn, since the opponent would be able to
+/// capture the king then.
+fn play_null(board: &mut Board) -> Result<State, GameError> {
+    let side = board.active_player;
+    if !king::is_safe(&board.map, board.get_king_pos(side), side) {
+        return Err(GameError::KingIsInCheck);
+    }
+
+    let state = State::new(board, NULL_TURN.to_string());
+
+    // En-passant is available only right after the pawn moved
+    board.enpassant = None;
+
+    Ok(state)
 }
 
 /// Play castling turn
@@ -92,7 +211,7 @@ fn play_castling(
 
 /// Checker whether castling rules are followed
 pub fn verify_castling(
-    board: &mut Board,
+    board: &Board,
     castling_type: CastlingType,
 ) -> Result<(), GameError> {
     let side = board.active_player;
@@ -144,7 +263,7 @@ fn play_pawn(board: &mut Board, turn: &mut Move) -> Result<State, GameError> {
         return Err(GameError::InvalidPawnMovement);
     }
 
-    let possible_src = movement::possible_squares_for_dst(
+    let src = movement::find_src(
         &board.map,
         turn.dst,
         board.active_player,
@@ -152,9 +271,8 @@ fn play_pawn(board: &mut Board, turn: &mut Move) -> Result<State, GameError> {
             true => PieceMove::PawnCapture,
             _ => PieceMove::PawnNormal,
         },
-    );
-
-    let src = movement::get_exact_src(possible_src, turn.src.take())?;
+        turn.src.take(),
+    )?;
 
     let is_enpassant = verify_pawn_capture(board, capture, turn.dst)?;
     let mut captured = board.move_piece(turn.dst, src);
@@ -167,6 +285,7 @@ fn play_pawn(board: &mut Board, turn: &mut Move) -> Result<State, GameError> {
 
     if let Some(promotion) = turn.promotion {
         board.map.insert(turn.dst, (promotion, board.active_player));
+        board.promoted |= board::square_bit(turn.dst);
     }
 
     state.moving_piece_src = Some(src);
@@ -193,9 +312,6 @@ fn play_pawn(board: &mut Board, turn: &mut Move) -> Result<State, GameError> {
         ));
     }
 
-    // Pawn movement resets this rule
-    board.fifty_move_rule = 0;
-
     Ok(state)
 }
 
@@ -204,14 +320,13 @@ fn play_piece(board: &mut Board, turn: &mut Move) -> Result<State, GameError> {
     let mut state = State::new(board, turn.to_string());
     let capture = turn.check_flag(Flag::CAPTURE);
 
-    let possible_src = movement::possible_squares_for_dst(
+    let src = movement::find_src(
         &board.map,
         turn.dst,
         board.active_player,
         PieceMove::from(turn.who),
-    );
-
-    let src = movement::get_exact_src(possible_src, turn.src.take())?;
+        turn.src.take(),
+    )?;
 
     verify_capture(board, capture, turn.dst)?;
     let captured = board.move_piece(turn.dst, src);
@@ -219,9 +334,6 @@ fn play_piece(board: &mut Board, turn: &mut Move) -> Result<State, GameError> {
     state.moving_piece_src = Some(src);
     if let Some(captured) = captured {
         state.captured = Some((turn.dst, captured));
-
-        // Any capture resets this counter
-        board.fifty_move_rule = 0;
     }
 
     if let Err(e) = verify_check_checkmate(board, turn.flags) {
@@ -285,11 +397,14 @@ fn verify_capture(
         Some((piece, side)) => match capture {
             false => Err(GameError::CaptureNotSet),
             _ => {
-                assert_ne!(piece, Piece::King, "King cannot be captured");
-
                 if side == board.active_player {
                     return Err(GameError::CaptureAlly);
                 }
+
+                // Only a broken position lets the king be captured
+                if piece == Piece::King {
+                    return Err(GameError::IllegalKingCapture);
+                }
                 Ok(())
             }
         },
@@ -310,7 +425,7 @@ fn can_opponent_do_any_turns(board: &mut Board) -> bool {
 
 /// Check status king status after the turn is played and check whether it is
 /// in accordance to what turn states
-fn verify_check_checkmate(
+pub fn verify_check_checkmate(
     board: &mut Board,
     flags: u8,
 ) -> Result<(), GameError> {
@@ -387,3 +502,72 @@ pub fn handle_castling_status(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::fen;
+    use super::*;
+
+    #[test]
+    fn irreversible_turn_clears_hashes() {
+        let mut board = fen::parse(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        )
+        .unwrap();
+
+        let state = next_turn(&mut board, "Nf3").unwrap();
+        assert!(!state.state_hashes_cleared);
+        next_turn(&mut board, "Nf6").unwrap();
+        assert_eq!(board.state_hashes.len(), 2);
+
+        let state = next_turn(&mut board, "e4").unwrap();
+        assert!(state.state_hashes_cleared);
+        assert_eq!(board.state_hashes.len(), 1);
+        assert_eq!(board.prev_state_hashes.len(), 1);
+
+        next_turn(&mut board, "Nxe4").unwrap();
+        assert_eq!(board.state_hashes.len(), 1);
+
+        board.restore_state_hashes();
+        board.restore_state_hashes();
+        assert_eq!(board.state_hashes.len(), 2);
+    }
+
+    #[test]
+    fn king_capture_rejected() {
+        let mut board = fen::parse("4k3/8/8/8/8/8/8/3Q1K2 w - - 0 1").unwrap();
+
+        // Let White play twice in a row to get at the black king
+        next_turn(&mut board, "Qe2+").unwrap();
+        board.active_player.switch_side();
+
+        assert!(matches!(
+            next_turn(&mut board, "Qxe8"),
+            Err(GameError::IllegalKingCapture)
+        ));
+    }
+
+    #[test]
+    fn move_number() {
+        assert_eq!(strip_move_number("12. Nf3"), "Nf3");
+        assert_eq!(strip_move_number("12.Nf3"), "Nf3");
+        assert_eq!(strip_move_number(" 7... exd5+ "), "exd5+");
+        assert_eq!(strip_move_number("1...0-0"), "0-0");
+
+        // Turns without the move number
+        assert_eq!(strip_move_number(" Nf3 "), "Nf3");
+        assert_eq!(strip_move_number("0-0-0"), "0-0-0");
+        assert_eq!(strip_move_number("12"), "12");
+    }
+
+    #[test]
+    fn annotation() {
+        assert_eq!(strip_annotation("Qxf7#!!"), "Qxf7#");
+        assert_eq!(strip_annotation("d8=B+?"), "d8=B+");
+        assert_eq!(strip_annotation("e4!?"), "e4");
+        assert_eq!(strip_annotation("e4?!"), "e4");
+        assert_eq!(strip_annotation("e4??"), "e4");
+        assert_eq!(strip_annotation("g1=Q!"), "g1=Q");
+        assert_eq!(strip_annotation("0-0"), "0-0");
+    }
+}
@@ -0,0 +1,113 @@
+use super::side::Side;
+use chess_notation_parser::Piece;
+
+/// Pieces which can be held in a pocket, kings are never captured
+const POCKET_PIECES: [Piece; 5] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+];
+
+/// Captured pieces of both sides available for drops
+///
+/// Used only by the Crazyhouse variant.
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct Pockets {
+    /// Number of pieces per side, in the order of `POCKET_PIECES`
+    counts: [[u8; POCKET_PIECES.len()]; 2],
+}
+
+impl Pockets {
+    /// Put the piece into the pocket of the `side`
+    pub fn add(&mut self, side: Side, piece: Piece) {
+        self.counts[side_index(side)][piece_index(piece)] += 1;
+    }
+
+    /// Take the piece out of the pocket of the `side`
+    ///
+    /// Return `false` if there is no such piece in the pocket.
+    pub fn remove(&mut self, side: Side, piece: Piece) -> bool {
+        let count = &mut self.counts[side_index(side)][piece_index(piece)];
+        match *count {
+            0 => false,
+            _ => {
+                *count -= 1;
+                true
+            }
+        }
+    }
+
+    /// Get every piece in the pocket of the `side`, each repeated as many
+    /// times as it's held
+    pub fn pieces(&self, side: Side) -> Vec<Piece> {
+        POCKET_PIECES
+            .iter()
+            .zip(self.counts[side_index(side)])
+            .flat_map(|(piece, count)| {
+                std::iter::repeat_n(*piece, count.into())
+            })
+            .collect()
+    }
+
+    /// Get kinds of pieces the `side` is able to drop
+    pub fn droppable(&self, side: Side) -> impl Iterator<Item = Piece> + '_ {
+        POCKET_PIECES
+            .iter()
+            .zip(self.counts[side_index(side)])
+            .filter(|(_, count)| *count > 0)
+            .map(|(piece, _)| *piece)
+    }
+
+    /// Swap pockets of both sides
+    pub fn swap_sides(&mut self) {
+        self.counts.swap(0, 1);
+    }
+}
+
+fn side_index(side: Side) -> usize {
+    match side {
+        Side::White => 0,
+        Side::Black => 1,
+    }
+}
+
+fn piece_index(piece: Piece) -> usize {
+    POCKET_PIECES
+        .iter()
+        .position(|p| *p == piece)
+        .expect("King cannot be held in a pocket")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_remove() {
+        let mut pockets = Pockets::default();
+        assert!(!pockets.remove(Side::White, Piece::Knight));
+
+        pockets.add(Side::White, Piece::Knight);
+        pockets.add(Side::White, Piece::Pawn);
+        pockets.add(Side::White, Piece::Knight);
+        pockets.add(Side::Black, Piece::Queen);
+
+        assert_eq!(
+            pockets.pieces(Side::White),
+            [Piece::Pawn, Piece::Knight, Piece::Knight]
+        );
+        assert_eq!(
+            pockets.droppable(Side::White).collect::<Vec<_>>(),
+            [Piece::Pawn, Piece::Knight]
+        );
+
+        assert!(pockets.remove(Side::White, Piece::Knight));
+        assert_eq!(pockets.pieces(Side::White), [Piece::Pawn, Piece::Knight]);
+
+        pockets.swap_sides();
+        assert_eq!(pockets.pieces(Side::White), [Piece::Queen]);
+        assert_eq!(pockets.pieces(Side::Black), [Piece::Pawn, Piece::Knight]);
+    }
+}
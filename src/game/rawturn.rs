@@ -0,0 +1,253 @@
+use super::availableturn::AvailableTurn;
+use super::piecedrop::PieceDrop;
+use super::side::Side;
+use chess_notation_parser::{turn_move, Flag, Piece, Square, Turn};
+use std::fmt;
+
+/// Lightweight variant of the `AvailableTurn`
+///
+/// Data is kept in the form it was calculated in, so no strings are allocated
+/// until they are really needed.
+///
+/// In case of a castling turn, king's source and destination squares will be
+/// provided. In case of a drop, source square is the same as the destination
+/// square.
+#[derive(Clone)]
+pub struct RawTurn {
+    /// Source square
+    pub src: Square,
+
+    /// Destination square
+    pub dst: Square,
+
+    /// Piece making the move
+    pub piece: Piece,
+
+    /// Side of the piece making the move
+    pub side: Side,
+
+    /// Captured piece
+    pub captured: Option<Piece>,
+
+    /// Turn which contains spoilers about the check and checkmate
+    turn: Turn,
+
+    /// Piece is dropped from the pocket instead of being moved
+    dropped: bool,
+}
+
+impl fmt::Display for RawTurn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", AvailableTurn::from(self))
+    }
+}
+
+impl From<&RawTurn> for AvailableTurn {
+    fn from(raw: &RawTurn) -> Self {
+        let mut turn = AvailableTurn::new(
+            raw.src.to_string(),
+            raw.dst.to_string(),
+            raw.piece.to_string(),
+            raw.captured.map(|piece| piece.to_string()),
+            raw.get_turn(),
+        );
+
+        turn.disambiguation = raw.disambiguation();
+        turn.captured_piece =
+            raw.captured.map(|piece| (piece, raw.side.opponent()));
+        turn
+    }
+}
+
+impl RawTurn {
+    /// Create `RawTurn`
+    pub fn new(
+        src: Square,
+        dst: Square,
+        piece: Piece,
+        side: Side,
+        captured: Option<Piece>,
+        turn: Turn,
+    ) -> RawTurn {
+        Self {
+            src,
+            dst,
+            piece,
+            side,
+            captured,
+            turn,
+            dropped: false,
+        }
+    }
+
+    /// Create `RawTurn` which drops the `piece` from the pocket onto `dst`
+    pub fn new_drop(
+        dst: Square,
+        piece: Piece,
+        side: Side,
+        flags: u8,
+    ) -> RawTurn {
+        Self {
+            src: dst,
+            dst,
+            piece,
+            side,
+            captured: None,
+            turn: turn_move!(piece, dst, flags),
+            dropped: true,
+        }
+    }
+
+    /// Fetch turn written in chess notation format
+    ///
+    /// Same as `AvailableTurn::get_turn`, the turn contains spoilers, so it's
+    /// not publicly provided in the struct
+    pub fn get_turn(&self) -> String {
+        match &self.turn {
+            Turn::Move(turn) if self.dropped => PieceDrop {
+                piece: turn.who,
+                dst: turn.dst,
+                flags: turn.flags,
+            }
+            .to_string(),
+            turn => turn.to_string(),
+        }
+    }
+
+    /// Check whether the piece is dropped from the pocket
+    pub fn is_drop(&self) -> bool {
+        self.dropped
+    }
+
+    /// Fetch the piece a pawn is promoted to
+    pub fn promotion(&self) -> Option<Piece> {
+        match &self.turn {
+            Turn::Move(turn) => turn.promotion,
+            Turn::Castling(_) => None,
+        }
+    }
+
+    /// Fetch the part of the source square written in the turn to tell apart
+    /// pieces of the same type, e.g. `d` in `Rdf8`
+    ///
+    /// Pawns are left out since their file is written only for captures.
+    pub fn disambiguation(&self) -> Option<String> {
+        let src = match &self.turn {
+            Turn::Move(turn) if turn.who != Piece::Pawn => turn.src.as_ref()?,
+            _ => return None,
+        };
+
+        // Source is either a single square, a whole file or a whole rank
+        match src.as_slice() {
+            [square] => Some(square.to_string()),
+            [first, second, ..]
+                if first.get_file_char() == second.get_file_char() =>
+            {
+                Some(first.get_file_char().to_string())
+            }
+            [first, ..] => Some(first.get_rank_char().to_string()),
+            [] => None,
+        }
+    }
+
+    /// Write the turn in the long algebraic format used by UCI, e.g. `e7e8q`
+    ///
+    /// Castling is written as the two-square move of the king and drops are
+    /// written like `N@e5`.
+    pub fn to_uci(&self) -> String {
+        if self.dropped {
+            return PieceDrop {
+                piece: self.piece,
+                dst: self.dst,
+                flags: Flag::NONE,
+            }
+            .to_string();
+        }
+
+        let promotion = match self.promotion() {
+            Some(Piece::Knight) => "n",
+            Some(Piece::Bishop) => "b",
+            Some(Piece::Rook) => "r",
+            Some(Piece::Queen) => "q",
+            _ => "",
+        };
+
+        format!("{}{}{}", self.src, self.dst, promotion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_available_turn() {
+        let raw = RawTurn::new(
+            Square::E4,
+            Square::D5,
+            Piece::Pawn,
+            Side::White,
+            Some(Piece::Knight),
+            Turn::try_from("exd5+").unwrap(),
+        );
+
+        let available_turn = AvailableTurn::from(&raw);
+        assert_eq!(available_turn.src, "e4");
+        assert_eq!(available_turn.dst, "d5");
+        assert_eq!(available_turn.piece, "Pawn");
+        assert_eq!(available_turn.captured, Some(String::from("Knight")));
+        assert!(matches!(
+            available_turn.captured_piece,
+            Some((Piece::Knight, Side::Black))
+        ));
+        assert_eq!(available_turn.get_turn(), "exd5+");
+        assert_eq!(raw.to_string(), available_turn.to_string());
+
+        let raw = RawTurn::new(
+            Square::G1,
+            Square::F3,
+            Piece::Knight,
+            Side::White,
+            None,
+            turn_move!(Piece::Knight, Square::F3, Flag::NONE),
+        );
+        assert_eq!(raw.get_turn(), "Nf3");
+        assert!(raw.promotion().is_none());
+        assert_eq!(raw.to_uci(), "g1f3");
+
+        let raw = RawTurn::new(
+            Square::B7,
+            Square::A8,
+            Piece::Pawn,
+            Side::White,
+            Some(Piece::Rook),
+            Turn::try_from("bxa8=N").unwrap(),
+        );
+        assert!(matches!(raw.promotion(), Some(Piece::Knight)));
+        assert_eq!(raw.to_uci(), "b7a8n");
+        assert!(raw.disambiguation().is_none());
+
+        for (turn, src, dst, disambiguation) in [
+            ("Rdf8", Square::D8, Square::F8, "d"),
+            ("R1a3", Square::A1, Square::A3, "1"),
+            ("Qd1f3", Square::D1, Square::F3, "d1"),
+        ] {
+            let turn = Turn::try_from(turn).unwrap();
+            let raw =
+                RawTurn::new(src, dst, Piece::Rook, Side::White, None, turn);
+            assert_eq!(raw.disambiguation().as_deref(), Some(disambiguation));
+        }
+
+        let raw = RawTurn::new_drop(
+            Square::E5,
+            Piece::Knight,
+            Side::Black,
+            Flag::CHECK,
+        );
+        assert!(raw.is_drop());
+        assert_eq!(raw.get_turn(), "N@e5+");
+        assert_eq!(raw.to_uci(), "N@e5");
+        assert!(raw.promotion().is_none());
+        assert!(raw.disambiguation().is_none());
+    }
+}
@@ -0,0 +1,202 @@
+use super::evaluation::{self, VICTORY_SCORE};
+use super::gamestate::Gamestate;
+use super::movement;
+use super::Game;
+
+/// Search for a forced mate for the player on turn
+///
+/// Every candidate turn is played and undone on the `game` itself, so the
+/// game is left in its original state once the search is finished.
+///
+/// # Arguments
+///
+/// * `game` - Game whose current position is searched
+/// * `max_depth` - Maximum number of full moves the mate may take
+pub fn find_mate(game: &mut Game, max_depth: usize) -> Option<Vec<String>> {
+    if max_depth == 0 || game.gamestate() != Gamestate::Ongoing {
+        return None;
+    }
+
+    for turn in collect_turns(game) {
+        // Turn comes from the list of available turns, so it must succeed
+        let gamestate = game
+            .play_turn_keep_redo(&turn)
+            .expect("Available turn failed");
+
+        let line = match gamestate {
+            Gamestate::Checkmate(_) => Some(vec![]),
+            Gamestate::Ongoing if max_depth > 1 => {
+                find_mate_for_every_reply(game, max_depth - 1)
+            }
+            _ => None,
+        };
+
+        game.undo_turn().expect("Undo of a played turn failed");
+
+        if let Some(mut line) = line {
+            line.insert(0, turn);
+            return Some(line);
+        }
+    }
+
+    None
+}
+
+/// Opponent is on turn. Every reply must be answered with a forced mate.
+fn find_mate_for_every_reply(
+    game: &mut Game,
+    max_depth: usize,
+) -> Option<Vec<String>> {
+    let mut mating_line = None;
+
+    for reply in collect_turns(game) {
+        game.play_turn_keep_redo(&reply)
+            .expect("Available turn failed");
+        let line = find_mate(game, max_depth);
+        game.undo_turn().expect("Undo of a played turn failed");
+
+        // One escape from the mate is enough to refute the whole line
+        let mut line = line?;
+
+        if mating_line.is_none() {
+            line.insert(0, reply);
+            mating_line = Some(line);
+        }
+    }
+
+    mating_line
+}
+
+/// Suggest the best turn for the player on turn using negamax search
+///
+/// Like with the mate search, turns are played and undone on the `game`.
+///
+/// # Arguments
+///
+/// * `game` - Game whose current position is searched
+/// * `depth` - Number of half moves to look ahead
+pub fn suggest_move(game: &mut Game, depth: usize) -> Option<String> {
+    if game.gamestate() != Gamestate::Ongoing {
+        return None;
+    }
+
+    let mut best: Option<(String, i32)> = None;
+
+    for turn in collect_turns(game) {
+        game.play_turn_keep_redo(&turn)
+            .expect("Available turn failed");
+        let score = -negamax(game, depth.saturating_sub(1));
+        game.undo_turn().expect("Undo of a played turn failed");
+
+        // The first of the equally good turns is kept
+        match best {
+            Some((_, best_score)) if best_score >= score => (),
+            _ => best = Some((turn, score)),
+        }
+    }
+
+    best.map(|(turn, _)| turn)
+}
+
+/// Score the position from the point of view of the player on turn
+fn negamax(game: &mut Game, depth: usize) -> i32 {
+    let sign = evaluation::side_sign(game.board.active_player);
+
+    match game.gamestate() {
+        // Player on turn has been mated, prefer the quicker mates
+        Gamestate::Checkmate(_) => return -(VICTORY_SCORE + depth as i32),
+        Gamestate::Ongoing if depth > 0 => (),
+        _ => return sign * evaluation::evaluate(game),
+    }
+
+    let mut best_score = i32::MIN;
+
+    for turn in collect_turns(game) {
+        game.play_turn_keep_redo(&turn)
+            .expect("Available turn failed");
+        let score = -negamax(game, depth - 1);
+        game.undo_turn().expect("Undo of a played turn failed");
+
+        best_score = best_score.max(score);
+    }
+
+    best_score
+}
+
+/// Pick a turn which neither hangs material nor allows a mate in one
+///
+/// Candidate turns are played and undone on a copy of the `game`. The first
+/// safe turn is picked. In case every turn loses material, the one losing
+/// the least is picked instead.
+///
+/// # Return value
+/// Index of the turn within the available turns
+pub fn safe_hint(game: &Game) -> Option<usize> {
+    let mut game = game.clone();
+    let mut best: Option<(usize, i32)> = None;
+
+    for (idx, turn) in collect_turns(&game).iter().enumerate() {
+        let won = game.available_turns_raw()[idx]
+            .captured
+            .map_or(0, evaluation::piece_value);
+
+        let gamestate = game
+            .play_turn_keep_redo(turn)
+            .expect("Available turn failed");
+        let lost = match gamestate {
+            Gamestate::Ongoing => opponent_best_gain(&game),
+            _ => 0,
+        };
+        game.undo_turn().expect("Undo of a played turn failed");
+
+        let balance = won - lost;
+        if balance >= 0 {
+            return Some(idx);
+        }
+
+        // The first of the equally bad turns is kept
+        match best {
+            Some((_, best_balance)) if best_balance >= balance => (),
+            _ => best = Some((idx, balance)),
+        }
+    }
+
+    best.map(|(idx, _)| idx)
+}
+
+/// Material the player on turn wins with their best immediate reply
+///
+/// Capture of a defended piece is expected to be answered by a recapture.
+fn opponent_best_gain(game: &Game) -> i32 {
+    let defender = game.board.active_player.opponent();
+
+    game.available_turns_raw()
+        .iter()
+        .map(|turn| {
+            if turn.get_turn().ends_with('#') {
+                return VICTORY_SCORE;
+            }
+
+            let captured = match turn.captured {
+                Some(captured) => evaluation::piece_value(captured),
+                None => return 0,
+            };
+
+            match movement::attackers(&game.board.map, turn.dst, defender)
+                .is_empty()
+            {
+                true => captured,
+                _ => (captured - evaluation::piece_value(turn.piece)).max(0),
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Copy out the available turns since the game is modified during the search
+pub fn collect_turns(game: &Game) -> Vec<String> {
+    game.available_turns_raw()
+        .iter()
+        .map(|turn| turn.get_turn())
+        .collect()
+}
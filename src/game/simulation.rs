@@ -2,11 +2,15 @@ use super::board::Board;
 use super::board_map::BoardMap;
 use super::castlinginfo;
 use super::enpassant::Enpassant;
+use super::gamestate::Gamestate;
 use super::king::{self, KingState};
 use super::movement::{self, PieceMove};
+use super::piecedrop::{self, PieceDrop};
 use super::play;
+use super::rawturn::RawTurn;
+use super::side::Side;
 use super::state::State;
-use super::{AvailableTurn, Side};
+use super::variant::GameVariant;
 use chess_notation_parser::{
     turn_castling, turn_move, Castling, CastlingType, Flag, FlagCheck, Move,
     Piece, Square, Turn,
@@ -20,70 +24,157 @@ struct Sps {
 }
 
 /// Return list of all possible turns that are valid and can be played
-pub fn get_available_turns(board: &mut Board) -> Vec<AvailableTurn> {
-    let mut available_turns = Vec::<AvailableTurn>::with_capacity(128);
+pub fn get_available_turns(board: &mut Board) -> Vec<RawTurn> {
+    let mut available_turns = Vec::<RawTurn>::with_capacity(128);
     let side = board.active_player;
 
-    // Square/Piece/Side
-    let player: Vec<Sps> = scan_for_pieces(&board.map, side);
+    // Simulations always restore the board, so a copy of the map is good
+    // enough for iterating over the pieces
+    for sps in scan_for_pieces(board.map, side) {
+        get_turns(&sps, board, &mut available_turns);
+    }
 
-    for sps in player.iter() {
-        let moves = &mut get_turns(sps, board);
-        available_turns.append(moves);
+    if board.variant == GameVariant::Crazyhouse {
+        get_drop_turns(board, &mut available_turns);
     }
 
     available_turns
 }
 
+/// Iterator which generates available turns one piece at a time
+///
+/// Turns are simulated on its own copy of the board, in the same order as
+/// `get_available_turns` returns them. Pieces not reached yet are not
+/// simulated at all, so stopping early skips the rest of the work.
+pub struct TurnGenerator {
+    /// Copy of the board the turns are simulated on
+    board: Board,
+
+    /// Pieces whose turns were not generated yet
+    pieces: std::vec::IntoIter<Sps>,
+
+    /// Generated turns of the latest piece
+    pending: std::vec::IntoIter<RawTurn>,
+
+    /// Drops are generated after all the pieces
+    drops_generated: bool,
+}
+
+impl TurnGenerator {
+    /// Create generator of the available turns of the player on turn
+    pub fn new(board: &Board) -> Self {
+        let pieces: Vec<Sps> = match board.gamestate {
+            Gamestate::Ongoing => {
+                scan_for_pieces(board.map, board.active_player).collect()
+            }
+            _ => vec![],
+        };
+
+        Self {
+            board: board.clone(),
+            pieces: pieces.into_iter(),
+            pending: vec![].into_iter(),
+            drops_generated: board.gamestate != Gamestate::Ongoing
+                || board.variant != GameVariant::Crazyhouse,
+        }
+    }
+}
+
+impl Iterator for TurnGenerator {
+    type Item = RawTurn;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(turn) = self.pending.next() {
+                return Some(turn);
+            }
+
+            let mut turns = Vec::<RawTurn>::new();
+            match self.pieces.next() {
+                Some(sps) => get_turns(&sps, &mut self.board, &mut turns),
+                None if !self.drops_generated => {
+                    self.drops_generated = true;
+                    get_drop_turns(&mut self.board, &mut turns);
+                }
+                None => return None,
+            }
+            self.pending = turns.into_iter();
+        }
+    }
+}
+
+/// Append all possible drops of the pieces from the pocket
+fn get_drop_turns(board: &mut Board, available_turns: &mut Vec<RawTurn>) {
+    let side = board.active_player;
+    let pieces: Vec<Piece> = board.pockets.droppable(side).collect();
+
+    for piece in pieces {
+        for dst in (0..64).map(Square::from) {
+            let simulated_state = match simulate_drop(board, piece, dst) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            let flags = get_check_flag(board);
+            available_turns.push(RawTurn::new_drop(dst, piece, side, flags));
+
+            board.active_player.switch_side();
+            board.hash_state_pop();
+            board.undo(simulated_state);
+        }
+    }
+}
+
 /// Find info for every piece for a given player
-fn scan_for_pieces(map: &BoardMap, side: Side) -> Vec<Sps> {
-    map.into_iter()
-        .filter(|(_, (_, s))| side == *s)
-        .map(|(sq, (p, _))| Sps {
+fn scan_for_pieces(map: BoardMap, side: Side) -> impl Iterator<Item = Sps> {
+    map.into_iter().filter(move |(_, (_, s))| side == *s).map(
+        move |(sq, (p, _))| Sps {
             square: sq,
             piece: p,
             side,
-        })
-        .collect::<Vec<Sps>>()
+        },
+    )
 }
 
-/// Preparation for `AvailableTurn` struct
+/// Preparation for `RawTurn` struct
 struct TurnInfo {
     captured: Option<Piece>,
     turn: Turn,
 }
 
-/// Get all possible turns for a given piece
-fn get_turns(sps: &Sps, board: &mut Board) -> Vec<AvailableTurn> {
+/// Append all possible turns for a given piece to `available_turns`
+fn get_turns(sps: &Sps, board: &mut Board, available_turns: &mut Vec<RawTurn>) {
     let unchecked_turns = get_unchecked_turns(sps, board);
     let mut turns = get_check_checkmate_flags(unchecked_turns, sps, board);
 
     set_correct_src(&mut turns, &board.map, sps);
-    gen_available_turns(turns, sps)
+    gen_available_turns(turns, sps, available_turns);
 }
 
 /// Generate available turns.
-/// Transform all `Turn' structs into `AvailableTurn` structs.
-fn gen_available_turns(turns: Vec<TurnInfo>, sps: &Sps) -> Vec<AvailableTurn> {
-    turns
-        .iter()
-        .map(|turn_info| {
-            let dst = match turn_info.turn {
-                Turn::Move(ref turn) => turn.dst,
-                Turn::Castling(castling) => {
-                    castlinginfo::get_path_king(sps.side, castling.r#type).dst
-                }
-            };
+/// Transform all `Turn' structs into `RawTurn` structs.
+fn gen_available_turns(
+    turns: Vec<TurnInfo>,
+    sps: &Sps,
+    available_turns: &mut Vec<RawTurn>,
+) {
+    available_turns.extend(turns.iter().map(|turn_info| {
+        let dst = match turn_info.turn {
+            Turn::Move(ref turn) => turn.dst,
+            Turn::Castling(castling) => {
+                castlinginfo::get_path_king(sps.side, castling.r#type).dst
+            }
+        };
 
-            AvailableTurn::new(
-                sps.square.to_string(),
-                dst.to_string(),
-                sps.piece.to_string(),
-                turn_info.captured.map(|piece| piece.to_string()),
-                turn_info.turn.to_string(),
-            )
-        })
-        .collect::<Vec<AvailableTurn>>()
+        RawTurn::new(
+            sps.square,
+            dst,
+            sps.piece,
+            sps.side,
+            turn_info.captured,
+            turn_info.turn.clone(),
+        )
+    }));
 }
 
 /// Decide what must be set as a `src` in given `Turn`
@@ -288,6 +379,36 @@ fn get_promotion_for_unchecked_pawn_turns(
     turns
 }
 
+/// Drop the piece if the square allows it and our king stays safe
+fn simulate_drop(
+    board: &mut Board,
+    piece: Piece,
+    dst: Square,
+) -> Result<State, ()> {
+    if !piecedrop::can_drop_on(board, piece, dst) {
+        return Err(());
+    }
+
+    let side = board.active_player;
+    let turn = PieceDrop {
+        piece,
+        dst,
+        flags: Flag::NONE,
+    };
+    let state = State::new(board, turn.to_string());
+    board.map.insert(dst, (piece, side));
+
+    if !king::is_safe(&board.map, board.get_king_pos(side), side) {
+        board.undo(state);
+        return Err(());
+    }
+
+    board.enpassant = None;
+    board.hash_state_push();
+    board.active_player.switch_side();
+    Ok(state)
+}
+
 /// Play the turn and check validity of the turn
 fn simulate_turn(
     board: &mut Board,
@@ -426,81 +547,94 @@ fn simulate_move(
 
 /// Update check and checkmate flags and prepare `captured` piece
 fn get_check_checkmate_flags(
-    mut turns: Vec<Turn>,
+    turns: Vec<Turn>,
     sps: &Sps,
     board: &mut Board,
 ) -> Vec<TurnInfo> {
-    let mut to_be_removed = Vec::<usize>::new();
-    let mut captured = Vec::<Option<Piece>>::with_capacity(turns.len());
+    let mut turn_infos = Vec::<TurnInfo>::with_capacity(turns.len());
 
-    for (i, turn) in turns.iter_mut().enumerate() {
+    for mut turn in turns {
         // `simulate_turn` swaps `active_player` side
-        let simulated_state = simulate_turn(board, sps, turn);
-        if simulated_state.is_err() {
-            captured.push(None);
-            to_be_removed.push(i);
-            continue;
-        }
+        let simulated_state = match simulate_turn(board, sps, &turn) {
+            Ok(state) => state,
+            // Skip those turns which endanger our king
+            Err(_) => continue,
+        };
 
-        match king::get_state(board, board.active_player) {
-            KingState::Safe => (),
-            KingState::Check => add_turn_flag(turn, Flag::CHECK),
-            // Check that king is really in checkmate
-            _ => add_turn_flag(
-                turn,
-                match confirm_checkmate(board) {
-                    true => Flag::CHECKMATE,
-                    _ => Flag::CHECK,
-                },
-            ),
-        }
+        add_turn_flag(&mut turn, get_check_flag(board));
 
-        let simulated_state = simulated_state.unwrap();
-        captured
-            .push(simulated_state.captured.map(|(_, (captured, _))| captured));
+        turn_infos.push(TurnInfo {
+            turn,
+            captured: simulated_state
+                .captured
+                .map(|(_, (captured, _))| captured),
+        });
 
         board.active_player.switch_side();
         board.hash_state_pop();
         board.undo(simulated_state);
     }
 
-    // Remove those turns which endanger our king
-    while let Some(i) = to_be_removed.pop() {
-        turns.remove(i);
-        captured.remove(i);
-    }
+    turn_infos
+}
 
-    turns
-        .into_iter()
-        .zip(captured.into_iter())
-        .map(|(turn, captured)| TurnInfo { turn, captured })
-        .collect::<Vec<TurnInfo>>()
+/// Get check or checkmate flag for the king of the player on turn
+fn get_check_flag(board: &mut Board) -> u8 {
+    match king::get_state(board, board.active_player) {
+        KingState::Safe => Flag::NONE,
+        KingState::Check => Flag::CHECK,
+        // Check that king is really in checkmate
+        _ => match confirm_checkmate(board) {
+            true => Flag::CHECKMATE,
+            _ => Flag::CHECK,
+        },
+    }
 }
 
 /// Opponent's king is in check and has no safe moves available
 /// Check if any opponent pieces can remove the check
 fn confirm_checkmate(board: &mut Board) -> bool {
-    // Let's act like this is N+1th turn on the board
-    let opponent: Vec<Sps> = scan_for_pieces(&board.map, board.active_player);
+    // Let's act like this is N+1th turn on the board.
+    // If we can play any simulated turn, it means that our king is not in
+    // check after our turn. It implies the turn has removed king from the
+    // check, therefore king was never in checkmate
+    !has_legal_turn(board)
+}
 
-    for sps in opponent {
+/// Check whether the player on turn can play any turn
+///
+/// Stops at the first legal turn, so it's cheaper than generating them all.
+pub fn has_legal_turn(board: &mut Board) -> bool {
+    for sps in scan_for_pieces(board.map, board.active_player) {
         for turn in get_unchecked_turns(&sps, board) {
-            match simulate_turn(board, &sps, &turn) {
-                // If we can play any simulated turn, it means that our king is
-                // not in check after our turn. It implies the turn has removed
-                // king from the check, therefore king was never in checkmate
-                Ok(state) => {
-                    board.active_player.switch_side();
-                    board.hash_state_pop();
-                    board.undo(state);
-                    return false;
-                }
-                _ => continue,
+            if let Ok(state) = simulate_turn(board, &sps, &turn) {
+                board.active_player.switch_side();
+                board.hash_state_pop();
+                board.undo(state);
+                return true;
             }
         }
     }
 
-    true
+    if board.variant != GameVariant::Crazyhouse {
+        return false;
+    }
+
+    // Drop might block the check
+    let pieces: Vec<Piece> =
+        board.pockets.droppable(board.active_player).collect();
+    for piece in pieces {
+        for dst in (0..64).map(Square::from) {
+            if let Ok(state) = simulate_drop(board, piece, dst) {
+                board.active_player.switch_side();
+                board.hash_state_pop();
+                board.undo(state);
+                return true;
+            }
+        }
+    }
+
+    false
 }
 
 /// Append turn flag
@@ -510,3 +644,25 @@ fn add_turn_flag(turn: &mut Turn, flag: u8) {
         Turn::Move(ref mut r#move) => r#move.flags |= flag,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn checkmate_confirmation() {
+        const BACK_RANK: &str = "h1,w,K a1,w,R g8,b,K f7,b,P g7,b,P h7,b,P";
+        let mut game = Game::new(BACK_RANK).unwrap();
+
+        let mate = |turns: Vec<RawTurn>| {
+            turns
+                .into_iter()
+                .find(|turn| turn.get_turn().starts_with("Ra8"))
+                .map(|turn| turn.get_turn().to_string())
+        };
+
+        let turns = get_available_turns(&mut game.board);
+        assert_eq!(mate(turns), Some("Ra8#".to_string()));
+    }
+}
@@ -1,7 +1,9 @@
+use super::board;
 use super::castling_rights::CastlingRights;
 use super::enpassant::Enpassant;
 use super::{Board, Side};
 use chess_notation_parser::{Piece, Square};
+use std::time::Duration;
 
 /// Turn theoretically can have this maximum size:
 ///  max 2 ascii chars for dst
@@ -20,6 +22,7 @@ const TURN_STR_MAX: usize = 12;
 ///
 /// Optimally, we would like to have this struct as small as possible since for
 /// every turn made, new state will be saved onto turn history memory stack.
+#[derive(Clone)]
 pub struct State {
     /// Track source to avoid its recalculation
     pub moving_piece_src: Option<Square>,
@@ -38,6 +41,22 @@ pub struct State {
     /// Castling rights
     pub castling_rights: CastlingRights,
 
+    /// Time spent on the turn, tracked only for timed turns
+    pub duration: Option<Duration>,
+
+    /// Turn was irreversible, so hashes of the previous board states were
+    /// put aside
+    pub state_hashes_cleared: bool,
+
+    /// Turn gave a check or a checkmate
+    pub was_check: bool,
+
+    /// Turn was a castling
+    pub was_castle: bool,
+
+    /// Squares holding promoted pieces before the turn
+    pub promoted: u64,
+
     /// From turn data, we can fetch demotion info
     turn: [u8; TURN_STR_MAX],
 }
@@ -58,6 +77,22 @@ impl State {
             fifty_move_rule: board.fifty_move_rule,
             castling_rights: board.castling_rights,
             captured: None,
+            duration: None,
+            state_hashes_cleared: false,
+            was_check: false,
+            was_castle: false,
+            promoted: board.promoted,
+        }
+    }
+
+    /// Get the piece which the capture puts into the pocket
+    ///
+    /// Captured promoted pieces are put into the pocket as pawns.
+    pub fn pocketed_piece(&self) -> Option<Piece> {
+        let (square, (piece, _)) = self.captured?;
+        match self.promoted & board::square_bit(square) != 0 {
+            true => Some(Piece::Pawn),
+            _ => Some(piece),
         }
     }
 
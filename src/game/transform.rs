@@ -0,0 +1,128 @@
+use super::board::{square_bit, Board};
+use super::board_map::BoardMap;
+use super::builder;
+use super::castling_rights::{CastlingRights, StartingRights};
+use super::enpassant::Enpassant;
+use super::gamestate::Gamestate;
+use super::side::Side;
+use chess_notation_parser::Square;
+use std::collections::{BTreeMap, HashMap};
+
+/// Flip the square vertically: e.g. a2 -> a7
+fn flip_rank(square: Square) -> Square {
+    Square::from(square as u8 ^ 56)
+}
+
+/// Reflect the square through the center of the board: e.g. a1 -> h8
+fn rotate_square(square: Square) -> Square {
+    Square::from(63 - square as u8)
+}
+
+/// Create a board rotated by 180 degrees
+///
+/// Pieces keep their colors, while castling and en-passant possibilities are
+/// dropped. The board comes without any stored state hashes and its gamestate
+/// is left for the game builder to find out.
+///
+/// Pawns keep moving in the same direction, so the rotated pawns may attack
+/// the king of the player not on turn. Such a board is rejected.
+pub fn rotate_180(board: &Board) -> Result<Board, &'static str> {
+    let mut map = BoardMap::new();
+    let mut king = HashMap::<Side, Square>::with_capacity(2);
+
+    let mut promoted = 0;
+    for (square, figure) in board.map {
+        map.insert(rotate_square(square), figure);
+        if board.promoted & square_bit(square) != 0 {
+            promoted |= square_bit(rotate_square(square));
+        }
+    }
+
+    for (side, square) in board.king.iter() {
+        king.insert(*side, rotate_square(*square));
+    }
+
+    let rotated = Board {
+        map,
+        active_player: board.active_player,
+        king,
+        enpassant: None,
+        castling_rights: CastlingRights::new(StartingRights::None),
+        gamestate: Gamestate::Ongoing,
+        fifty_move_rule: board.fifty_move_rule,
+        fifty_move_limit: board.fifty_move_limit,
+        detect_draws: board.detect_draws,
+        state_hashes: BTreeMap::new(),
+        prev_state_hashes: vec![],
+        variant: board.variant,
+        pockets: board.pockets,
+        promoted,
+    };
+
+    match builder::is_idle_king_safe(&rotated) {
+        true => Ok(rotated),
+        _ => Err("King of the player not on turn must not be in check"),
+    }
+}
+
+/// Create a board where both players swap their pieces and their turn
+///
+/// Every piece is moved to the same file on the opposite rank and changes
+/// its side. The board comes without any stored state hashes.
+pub fn mirror_colors(board: &Board) -> Board {
+    let mut map = BoardMap::new();
+    let mut king = HashMap::<Side, Square>::with_capacity(2);
+
+    let mut promoted = 0;
+    for (square, (piece, side)) in board.map {
+        map.insert(flip_rank(square), (piece, side.opponent()));
+        if board.promoted & square_bit(square) != 0 {
+            promoted |= square_bit(flip_rank(square));
+        }
+    }
+
+    for (side, square) in board.king.iter() {
+        king.insert(side.opponent(), flip_rank(*square));
+    }
+
+    let mut castling_rights = CastlingRights::new(StartingRights::None);
+    for (side, castling_type) in board.castling_rights.get() {
+        castling_rights.insert((side.opponent(), castling_type));
+    }
+
+    let enpassant = board.enpassant.map(|enpassant| Enpassant {
+        pawn_src: flip_rank(enpassant.pawn_src),
+        capture_pos: flip_rank(enpassant.capture_pos),
+    });
+
+    let swap_winner = |side: &String| match side == &Side::White.to_string() {
+        true => Side::Black.to_string(),
+        _ => Side::White.to_string(),
+    };
+
+    let mut pockets = board.pockets;
+    pockets.swap_sides();
+
+    let gamestate = match &board.gamestate {
+        Gamestate::Victory(side) => Gamestate::Victory(swap_winner(side)),
+        Gamestate::Checkmate(side) => Gamestate::Checkmate(swap_winner(side)),
+        gamestate => gamestate.clone(),
+    };
+
+    Board {
+        map,
+        active_player: board.active_player.opponent(),
+        king,
+        enpassant,
+        castling_rights,
+        gamestate,
+        fifty_move_rule: board.fifty_move_rule,
+        fifty_move_limit: board.fifty_move_limit,
+        detect_draws: board.detect_draws,
+        state_hashes: BTreeMap::new(),
+        prev_state_hashes: vec![],
+        variant: board.variant,
+        pockets,
+        promoted,
+    }
+}
@@ -0,0 +1,107 @@
+use crate::ChessTurnEngine;
+use std::collections::HashMap;
+
+/// Cache of values computed for positions, e.g. scores found by a search
+///
+/// Entries are keyed by [`ChessTurnEngine::position_key`]. Position of every
+/// entry is also kept as the first four fields of its FEN record, so a hash
+/// collision never returns a value stored for a different position. Storing
+/// a value for a colliding position replaces the previous entry.
+///
+/// Same positions reached by different turn orders share the entry, while
+/// the halfmove clock and the full move number are ignored.
+#[derive(Clone)]
+pub struct TranspositionTable<V> {
+    /// Verification FEN and the value of every stored position
+    entries: HashMap<u64, (String, V)>,
+}
+
+impl<V> Default for TranspositionTable<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> TranspositionTable<V> {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Create an empty table with space for at least `capacity` positions
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Store the `value` for the current position of the `engine`
+    ///
+    /// # Return value
+    ///  - `Some` - Value previously stored for the same position
+    ///  - `None` - Position was not stored yet
+    pub fn insert(&mut self, engine: &ChessTurnEngine, value: V) -> Option<V> {
+        let fen = position_fen(engine);
+        match self
+            .entries
+            .insert(engine.position_key(), (fen.clone(), value))
+        {
+            Some((prev_fen, prev_value)) if prev_fen == fen => Some(prev_value),
+            _ => None,
+        }
+    }
+
+    /// Get the value stored for the current position of the `engine`
+    pub fn get(&self, engine: &ChessTurnEngine) -> Option<&V> {
+        self.entries
+            .get(&engine.position_key())
+            .filter(|(fen, _)| *fen == position_fen(engine))
+            .map(|(_, value)| value)
+    }
+
+    /// Get the mutable value stored for the current position of the
+    /// `engine`
+    pub fn get_mut(&mut self, engine: &ChessTurnEngine) -> Option<&mut V> {
+        let fen = position_fen(engine);
+        self.entries
+            .get_mut(&engine.position_key())
+            .filter(|(stored_fen, _)| *stored_fen == fen)
+            .map(|(_, value)| value)
+    }
+
+    /// Remove the value stored for the current position of the `engine`
+    pub fn remove(&mut self, engine: &ChessTurnEngine) -> Option<V> {
+        self.get(engine)?;
+        self.entries
+            .remove(&engine.position_key())
+            .map(|(_, value)| value)
+    }
+
+    /// Get number of stored positions
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether no position is stored
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all stored positions
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Get the position part of the FEN record: placement, side to move,
+/// castling and en-passant
+fn position_fen(engine: &ChessTurnEngine) -> String {
+    engine
+        .fen()
+        .split_whitespace()
+        .take(4)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,67 @@
+use super::castlinginfo;
+use super::piecedrop::PieceDrop;
+use super::play;
+use super::side::Side;
+use super::state::State;
+use chess_notation_parser::{Piece, Square, Turn};
+
+/// Details about the undone turn, e.g. to animate it in the UI
+#[derive(Clone)]
+pub struct UndoInfo {
+    /// Square the moving piece returns to
+    ///
+    /// It's `None` for drops, since the piece goes back to the pocket, and
+    /// for null turns.
+    pub from: Option<Square>,
+
+    /// Square the moving piece leaves
+    ///
+    /// It's `None` for null turns.
+    pub to: Option<Square>,
+
+    /// Moving piece as it was before the turn, e.g. a pawn for promotions
+    /// and a king for castling
+    ///
+    /// It's `None` for null turns.
+    pub moved_piece: Option<Piece>,
+
+    /// Captured piece which reappears on the board along with its square
+    ///
+    /// Square differs from the `to` square for en-passant captures.
+    pub restored_piece: Option<(Square, Piece)>,
+}
+
+impl UndoInfo {
+    /// Collect details of the turn from its state and the `side` which
+    /// played it
+    pub fn new(state: &State, side: Side) -> Self {
+        let turn = state.get_turn();
+
+        let (from, to, moved_piece) = if turn == play::NULL_TURN {
+            (None, None, None)
+        } else if let Ok(drop) = PieceDrop::try_from(turn) {
+            (None, Some(drop.dst), Some(drop.piece))
+        } else {
+            // Turns in the history were already played, so unwrap!
+            match Turn::try_from(turn).unwrap() {
+                Turn::Castling(castling) => {
+                    let path =
+                        castlinginfo::get_path_king(side, castling.r#type);
+                    (Some(path.src), Some(path.dst), Some(Piece::King))
+                }
+                Turn::Move(turn) => {
+                    (state.moving_piece_src, Some(turn.dst), Some(turn.who))
+                }
+            }
+        };
+
+        Self {
+            from,
+            to,
+            moved_piece,
+            restored_piece: state
+                .captured
+                .map(|(square, (piece, _))| (square, piece)),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// Rules the game is played by
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum GameVariant {
+    /// Standard chess
+    #[default]
+    Standard,
+
+    /// Captured pieces go into the pocket of the capturing side and can be
+    /// dropped back onto empty squares instead of making a move, e.g. `N@e5`
+    ///
+    /// Captured promoted pieces go into the pocket as pawns.
+    Crazyhouse,
+}
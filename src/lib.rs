@@ -41,6 +41,9 @@
 //! ### `ViewMode::FancyTui` - Colorful terminal ASCII display format
 //! Note: *colors are not visible in documentation pages*
 //!
+//! Colors can be changed with `ViewMode::FancyTuiColors` and a `ColorScheme`,
+//! e.g. `ColorScheme::LIGHT` suits terminals with a light background.
+//!
 //! #### Example
 //! ```text
 //! 8  ♜  ♞  ♝  ♛  ♚  ♝  ♞  ♜
@@ -141,25 +144,118 @@
 
 mod game;
 
+use chess_notation_parser::CastlingType;
+pub use chess_notation_parser::{Piece, Square};
 pub use game::availableturn::AvailableTurn;
+pub use game::checkstatus::CheckStatus;
+pub use game::clock::Clock;
+pub use game::epd::EpdOps;
+pub use game::evasion::CheckEvasionKind;
+pub use game::filestatus::FileStatus;
 pub use game::game_error::GameError;
 pub use game::gamestate::Gamestate;
+pub use game::moverecord::MoveRecord;
+pub use game::pawnstructure::PawnStructure;
+pub use game::rawturn::RawTurn;
+pub use game::side::Side;
+pub use game::transposition::TranspositionTable;
+pub use game::undoinfo::UndoInfo;
+pub use game::variant::GameVariant;
 use game::Game;
+use std::collections::BTreeMap;
+use std::ops::Index;
+use std::time::Duration;
 
 /// Game engine
+///
+/// Engine owns all of its data, so it is `Send` and `Sync` and can be shared
+/// between threads, e.g. behind an `Arc` for analysis.
+#[derive(Clone)]
 pub struct ChessTurnEngine {
     /// Game
     game: Game,
 }
 
+/// Saved game state which can be restored with [`ChessTurnEngine::restore`]
+///
+/// Checkpoint holds a complete copy of the game, including the turn history,
+/// so it stays valid no matter which turns are played or undone after it was
+/// taken.
+#[derive(Clone)]
+pub struct GameCheckpoint {
+    /// Copy of the game
+    game: Game,
+}
+
 /// View modes for `BoardView`
 #[derive(Copy, Clone)]
 pub enum ViewMode {
     /// Display board in plain ASCII format
     SimpleAscii,
 
+    /// Same as `SimpleAscii`, but all empty squares are displayed as ` . `
+    SimpleAsciiPlain,
+
+    /// Same as `SimpleAscii`, but ranks are displayed on both sides and files
+    /// both above and below the board
+    SimpleAsciiBordered,
+
     /// Display board in colorful ASCII format for terminal purposes
     FancyTui,
+
+    /// Same as `FancyTui`, but with the colors taken from the `ColorScheme`
+    FancyTuiColors(ColorScheme),
+}
+
+/// ANSI escape codes used by the `FancyTui` view mode
+///
+/// Square codes set both the background and the foreground color, so pieces
+/// stay visible on them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ColorScheme {
+    /// Light squares, e.g. `h1`
+    pub light_square: &'static str,
+
+    /// Dark squares, e.g. `a1`
+    pub dark_square: &'static str,
+
+    /// Frame around the board
+    pub grid: &'static str,
+
+    /// Rank and file labels within the frame
+    pub labels: &'static str,
+}
+
+impl ColorScheme {
+    /// Scheme for terminals with a dark background, used by `FancyTui`
+    pub const DARK: ColorScheme = ColorScheme {
+        light_square: "\x1b[40m\x1b[37m",
+        dark_square: "\x1b[47m\x1b[30m",
+        grid: "\x1b[44m",
+        labels: "\x1b[1;37m",
+    };
+
+    /// Scheme for terminals with a light background
+    pub const LIGHT: ColorScheme = ColorScheme {
+        light_square: "\x1b[107m\x1b[30m",
+        dark_square: "\x1b[47m\x1b[30m",
+        grid: "\x1b[100m",
+        labels: "\x1b[1;97m",
+    };
+
+    /// Scheme with bright colors and bold pieces
+    pub const HIGH_CONTRAST: ColorScheme = ColorScheme {
+        light_square: "\x1b[107m\x1b[1;30m",
+        dark_square: "\x1b[40m\x1b[1;97m",
+        grid: "\x1b[41m",
+        labels: "\x1b[1;93m",
+    };
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::DARK
+    }
 }
 
 /// Display option
@@ -171,8 +267,27 @@ pub enum DisplayOption {
     /// Display turn history in plain ASCII format
     TurnHistory,
 
+    /// Display turn history as a single line of space separated movetext,
+    /// e.g. `1. e4 e5 2. Nf3 Nc6`
+    ///
+    /// Unlike [`DisplayOption::TurnHistory`] there are no tabs or newlines,
+    /// so it's suitable for embedding in text.
+    Movetext,
+
     /// Display capture history in plain ASCII format
+    ///
+    /// Captured points of both players and the material difference are shown
+    /// at the end.
     CaptureHistory,
+
+    /// Display captured pieces grouped by the capturing player
+    CaptureHistoryBySide,
+
+    /// Display board as an HTML table
+    ///
+    /// Every cell carries a `data-square` attribute with the square name:
+    /// e.g. `data-square="e4"`
+    Html,
 }
 
 /// Chessboard setup
@@ -228,6 +343,185 @@ impl ChessTurnEngine {
         Ok(ChessTurnEngine { game })
     }
 
+    /// Create and setup a new game played by the rules of the `variant`
+    ///
+    /// In [`GameVariant::Crazyhouse`], captured pieces go into the pocket of
+    /// the capturing side. Instead of making a move, a piece from the pocket
+    /// can be dropped onto an empty square, e.g. `N@e5` or `P@e4` (`@e4`
+    /// works as well). Pawns cannot be dropped on the first or the last rank
+    /// and a drop cannot leave our king in check. Captured promoted pieces
+    /// are not turned back into pawns.
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - Game setup
+    /// * `variant` - Rules the game is played by
+    pub fn with_variant(
+        setup: Setup,
+        variant: GameVariant,
+    ) -> Result<ChessTurnEngine, &'static str> {
+        let setup = match setup {
+            Setup::Normal => game::NORMAL_SETUP,
+            Setup::Custom(setup) => setup,
+        };
+
+        let game = Game::with_variant(setup, variant)?;
+        Ok(ChessTurnEngine { game })
+    }
+
+    /// Create a new game out of a FEN record.
+    ///
+    /// Castling rights, en-passant possibility and the halfmove clock are
+    /// taken from the record. Turn history starts empty.
+    ///
+    /// # Return value
+    ///  - `Ok` - New engine with the given position
+    ///  - `Err` - `GameError::ParsingFenFailed` with the reason
+    ///
+    /// # Arguments
+    ///
+    /// * `fen` - FEN record, e.g.
+    ///   `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1`
+    pub fn from_fen(fen: &str) -> Result<ChessTurnEngine, GameError> {
+        let game = Game::from_fen(fen)?;
+        Ok(ChessTurnEngine { game })
+    }
+
+    /// Create a new game out of a start position and a list of moves in the
+    /// UCI format, same as the `position` command of the UCI protocol.
+    ///
+    /// # Return value
+    ///  - `Ok` - New engine with all the moves played
+    ///  - `Err` - `GameError::ParsingFenFailed` for an invalid FEN record or
+    ///    the error of the first move which cannot be played, e.g.
+    ///    `GameError::IllegalUciMove`
+    ///
+    /// # Arguments
+    ///
+    /// * `start_fen` - FEN record of the start position, the normal setup
+    ///   is used in case of `None`
+    /// * `moves` - Moves in the long algebraic format, e.g. `["e2e4", "e7e5"]`
+    pub fn from_uci(
+        start_fen: Option<&str>,
+        moves: &[&str],
+    ) -> Result<ChessTurnEngine, GameError> {
+        let mut game = match start_fen {
+            Some(fen) => Game::from_fen(fen)?,
+            None => Game::new(game::NORMAL_SETUP).expect("Valid normal setup"),
+        };
+
+        for uci in moves {
+            game.play_uci(uci)?;
+        }
+
+        Ok(ChessTurnEngine { game })
+    }
+
+    /// Create a new game out of 64 encoded squares, the inverse of
+    /// [`ChessTurnEngine::board_bytes`].
+    ///
+    /// No strings are parsed, so it's a fast way to resume a game from a
+    /// compact snapshot, e.g. over FFI. Castling and en-passant are not
+    /// available and turn history starts empty.
+    ///
+    /// # Return value
+    ///  - `Ok` - New engine with the given position
+    ///  - `Err` - `GameError::InvalidBoardBytes` if a byte is not a valid
+    ///    encoded square, either side doesn't have exactly one king or the
+    ///    king of the player not on turn is in check
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Encoded squares from `a8` (0) to `h1` (63)
+    /// * `active` - Player on turn
+    pub fn from_board_bytes(
+        bytes: [u8; 64],
+        active: Side,
+    ) -> Result<ChessTurnEngine, GameError> {
+        let game = Game::from_board_bytes(bytes, active)?;
+        Ok(ChessTurnEngine { game })
+    }
+
+    /// Create a new game out of an EPD record.
+    ///
+    /// EPD record consists of the first four FEN fields followed by
+    /// operations, e.g. `bm Qg6; id "WAC.001";`. Halfmove clock is taken
+    /// from the `hmvc` operation and defaults to zero.
+    ///
+    /// # Return value
+    ///  - `Ok` - New engine with the given position and parsed operations
+    ///  - `Err` - `GameError::ParsingFenFailed` with the reason
+    ///
+    /// # Arguments
+    ///
+    /// * `epd` - EPD record
+    pub fn from_epd(epd: &str) -> Result<(ChessTurnEngine, EpdOps), GameError> {
+        let (game, ops) = Game::from_epd(epd)?;
+        Ok((ChessTurnEngine { game }, ops))
+    }
+
+    /// Create a new game from the default board setup by replaying turns
+    /// encoded with [`ChessTurnEngine::to_move_bytes`].
+    ///
+    /// # Return value
+    ///  - `Ok` - New engine with all the turns played
+    ///  - `Err` - `GameError` of the first turn that couldn't be played, or
+    ///    `GameError::ParsingTurnFailed` if the bytes don't describe a
+    ///    legal turn
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Turns encoded into 2 bytes each
+    pub fn from_move_bytes(bytes: &[u8]) -> Result<ChessTurnEngine, GameError> {
+        let game = Game::from_move_bytes(bytes)?;
+        Ok(ChessTurnEngine { game })
+    }
+
+    /// Encode played turns into a compact binary stream.
+    ///
+    /// Every turn takes 2 bytes (little endian): 6 bits for the source
+    /// square, 6 bits for the destination square and 3 bits for the
+    /// promotion piece. Castling is stored as the king's move. Stream can be
+    /// replayed only on the default board setup with
+    /// [`ChessTurnEngine::from_move_bytes`].
+    ///
+    /// Neither the setup nor the variant is stored, so games started from a
+    /// custom setup or a FEN record and Crazyhouse games cannot be encoded.
+    ///
+    /// # Return value
+    ///  - `Ok` - Encoded turns
+    ///  - `Err` - `GameError::UnsupportedMoveBytesSetup` if the game didn't
+    ///    start from the default setup in the standard variant
+    pub fn to_move_bytes(&self) -> Result<Vec<u8>, GameError> {
+        self.game.to_move_bytes()
+    }
+
+    /// Validate a whole game played from the default board setup
+    ///
+    /// Turns are played one by one on a new game which is dropped afterwards.
+    ///
+    /// # Return value
+    ///  - `Ok` - Game status after the last turn
+    ///  - `Err` - Zero-based index of the first turn that failed along with
+    ///    the `GameError`
+    ///
+    /// # Arguments
+    ///
+    /// * `moves` - Turns provided in chess notation format
+    pub fn validate_game(
+        moves: &[&str],
+    ) -> Result<Gamestate, (usize, GameError)> {
+        let mut game =
+            Game::new(game::NORMAL_SETUP).expect("Default setup is valid");
+        let mut gamestate = game.gamestate();
+
+        for (i, turn) in moves.iter().enumerate() {
+            gamestate = game.play_turn(turn).map_err(|e| (i, e))?;
+        }
+
+        Ok(gamestate)
+    }
+
     /// Provide a displayable `String` of the chessboard
     ///
     /// # Arguments
@@ -248,6 +542,9 @@ impl ChessTurnEngine {
 
     /// Play provided turn.
     ///
+    /// Leading move number is ignored, so turns copied along with it, e.g.
+    /// `12. Nf3` or `12... Nf6`, can be played as well.
+    ///
     /// # Return value
     ///  - `Ok` - Latest game status.
     ///  - `Err` - `GameError` in case turn is not playable.
@@ -259,18 +556,1313 @@ impl ChessTurnEngine {
         self.game.play_turn(turn)
     }
 
+    /// Play turn written in the long algebraic format used by UCI.
+    ///
+    /// Castling is written as the two-square move of the king, e.g. `e1g1`,
+    /// and promotions with the lowercase piece letter, e.g. `e7e8q`.
+    ///
+    /// # Return value
+    ///  - `Ok` - Latest game status.
+    ///  - `Err` - `GameError::IllegalUciMove` in case no available turn
+    ///    matches the move.
+    ///
+    /// # Arguments
+    ///
+    /// * `uci` - Move in the long algebraic format, e.g. `g1f3`
+    pub fn play_uci(&mut self, uci: &str) -> Result<Gamestate, GameError> {
+        self.game.play_uci(uci)
+    }
+
+    /// Play whitespace separated turns one by one, e.g. `1. e4 e5 2. Nf3`.
+    ///
+    /// Playing stops at the first turn which fails, while the turns played
+    /// before it are kept on the board.
+    ///
+    /// # Return value
+    ///  - `Ok` - Latest game status.
+    ///  - `Err` - Failed turn along with its `GameError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `turns` - Turns provided in chess notation format
+    pub fn play_sequence(
+        &mut self,
+        turns: &str,
+    ) -> Result<Gamestate, (String, GameError)> {
+        self.game.play_sequence(turns)
+    }
+
+    /// Play the game until it's over, e.g. for self-play.
+    ///
+    /// In every position, `select` is asked to pick the index of the turn to
+    /// play out of the available turns. It can pick turns at random, by
+    /// evaluation or by a script.
+    ///
+    /// Playing stops after `max_plies` turns even if the game is still going
+    /// on, since the game may never end once the draws are not detected.
+    ///
+    /// # Return value
+    /// Gamestate once the playing stops
+    ///
+    /// # Panics
+    ///
+    /// Panics if `select` returns an index out of the given turns.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_plies` - Maximum number of turns to play
+    /// * `select` - Picks the index of the turn to play
+    pub fn play_out<F>(&mut self, max_plies: usize, select: F) -> Gamestate
+    where
+        F: FnMut(&[AvailableTurn]) -> usize,
+    {
+        self.game.play_out(max_plies, select)
+    }
+
+    /// Play provided turn without accepting the leading move number.
+    ///
+    /// # Return value
+    ///  - `Ok` - Latest game status.
+    ///  - `Err` - `GameError` in case turn is not playable.
+    ///
+    /// # Arguments
+    ///
+    /// * `turn` - Turn provided in chess notation format
+    pub fn play_turn_strict(
+        &mut self,
+        turn: &str,
+    ) -> Result<Gamestate, GameError> {
+        self.game.play_turn_strict(turn)
+    }
+
+    /// Play provided turn and record how long it took to make it.
+    ///
+    /// Durations are kept in the turn history and can be fetched with
+    /// [`ChessTurnEngine::move_durations`].
+    ///
+    /// In case the clock is set with [`ChessTurnEngine::set_clock`], the
+    /// `elapsed` time is subtracted from the player's remaining time and the
+    /// increment is added. If the player runs out of time, the opponent wins
+    /// the game, unless the opponent has only the king left which makes the
+    /// game a draw.
+    ///
+    /// # Return value
+    ///  - `Ok` - Latest game status.
+    ///  - `Err` - `GameError` in case turn is not playable.
+    ///
+    /// # Arguments
+    ///
+    /// * `turn` - Turn provided in chess notation format
+    /// * `elapsed` - Time spent on the turn
+    pub fn play_turn_timed(
+        &mut self,
+        turn: &str,
+        elapsed: Duration,
+    ) -> Result<Gamestate, GameError> {
+        self.game.play_turn_timed(turn, elapsed)
+    }
+
+    /// Change the number of half moves without a capture or a pawn move
+    /// which ends the game in a draw.
+    ///
+    /// Default limit is 100 half moves, i.e. fifty moves of each player.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Number of half moves
+    pub fn set_fifty_move_limit(&mut self, limit: u8) {
+        self.game.set_fifty_move_limit(limit)
+    }
+
+    /// Enable or disable draw detection, e.g. for puzzles.
+    ///
+    /// Once disabled, the fifty move rule, threefold repetition and
+    /// insufficient mating material don't end the game, so it can end only
+    /// by a checkmate or a stalemate. Draws are detected by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `detect` - Whether the draws end the game
+    pub fn detect_draws(&mut self, detect: bool) {
+        self.game.detect_draws(detect)
+    }
+
+    /// Enable or disable calculating available turns right after each turn.
+    ///
+    /// Generating available turns is the most expensive part of playing a
+    /// turn. Once disabled, they are calculated only when they are requested
+    /// for the first time in the current position, e.g. by
+    /// `available_turns`. Bulk processing which rarely needs them, like
+    /// replaying games, gets faster. Game ending is detected either way.
+    /// Eager generation is enabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `eager` - Whether available turns are calculated after each turn
+    pub fn eager_move_generation(&mut self, eager: bool) {
+        self.game.eager_move_generation(eager)
+    }
+
+    /// Get number of half moves played since the last capture or pawn move.
+    ///
+    /// This is the counter used by the fifty move rule and the FEN halfmove
+    /// clock. It's `0` right after the capture or the pawn move. Other turns,
+    /// castling included, increase it by one.
+    pub fn halfmoves_since_irreversible(&self) -> u8 {
+        self.game.halfmoves_since_irreversible()
+    }
+
+    /// Check whether the fifty move rule is about to end the game.
+    ///
+    /// # Return value
+    ///  - `true` - Game ends in a draw within `within` half moves unless a
+    ///    capture or a pawn move is played
+    ///  - `false` - Otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `within` - Number of half moves
+    pub fn is_near_fifty_move_draw(&self, within: u8) -> bool {
+        self.game.is_near_fifty_move_draw(within)
+    }
+
+    /// Get how many times the current position has occurred.
+    ///
+    /// This is the count used by the threefold repetition rule, so the game
+    /// is drawn once it reaches `3`. Only positions reached since the last
+    /// capture or pawn move are counted and the starting position of the game
+    /// is not counted until it's reached again by a turn.
+    pub fn repetition_count(&self) -> u8 {
+        self.game.repetition_count()
+    }
+
+    /// Get a hash of the current position, e.g. as a key of a search cache.
+    ///
+    /// Pieces, the player on turn, castling rights, en-passant possibility
+    /// and pockets are hashed, the same parts compared by the threefold
+    /// repetition rule. Different positions may share the key, see
+    /// [`TranspositionTable`] which takes care of that. Keys are stable
+    /// within the same build, but they should not be persisted.
+    pub fn position_key(&self) -> u64 {
+        self.game.position_key()
+    }
+
+    /// Start the chess clock.
+    ///
+    /// Only turns played with [`ChessTurnEngine::play_turn_timed`] are
+    /// charged. Undoing the turn restores the clock as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Clock with the starting time and increment
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.game.set_clock(clock)
+    }
+
+    /// Get remaining time of the player.
+    ///
+    /// # Return value
+    ///  - `Some` - Remaining time on the player's clock
+    ///  - `None` - Clock is not set
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Player whose time is requested
+    pub fn time_remaining(&self, side: Side) -> Option<Duration> {
+        self.game.time_remaining(side)
+    }
+
     /// Get game status
     pub fn gamestate(&self) -> Gamestate {
         self.game.gamestate()
     }
 
-    /// Undo turn and restore previous board state
+    /// Get whether the king of the player on turn is safe, in check or
+    /// checkmated.
+    ///
+    /// Checkmate is reported only when no legal turn stops the check, which
+    /// is consistent with [`ChessTurnEngine::gamestate`].
+    pub fn check_status(&self) -> CheckStatus {
+        self.game.check_status()
+    }
+
+    /// Check whether the game ended with a checkmate
+    pub fn in_checkmate(&self) -> bool {
+        matches!(self.game.gamestate(), Gamestate::Checkmate(_))
+    }
+
+    /// Check whether the game ended with a stalemate
+    pub fn in_stalemate(&self) -> bool {
+        self.game.gamestate() == Gamestate::Stalemate
+    }
+
+    /// Get game result as a PGN result token.
+    ///
+    /// # Return value
+    ///  - `1-0` - White won
+    ///  - `0-1` - Black won
+    ///  - `1/2-1/2` - Game ended in a draw
+    ///  - `*` - Game is still ongoing
+    pub fn result_token(&self) -> &'static str {
+        self.game.result_token()
+    }
+
+    /// Pass the turn to the opponent without moving any piece.
+    ///
+    /// Useful for analysis, e.g. to check the threats of the opponent. It's
+    /// recorded in the history as the null turn `--`, which may be played
+    /// with [`ChessTurnEngine::play_turn`] as well, and it's undone with
+    /// [`ChessTurnEngine::undo_turn`]. En-passant possibility is lost.
+    ///
+    /// # Return value
+    ///  - `Ok` - Gamestate after the turn was passed
+    ///  - `Err` - `GameError::KingIsInCheck` if the player on turn is in
+    ///    check, since the opponent would be able to capture the king
+    pub fn toggle_side_to_move(&mut self) -> Result<Gamestate, GameError> {
+        self.game.toggle_side_to_move()
+    }
+
+    /// Undo turn and restore previous board state.
+    ///
+    /// Undone turn can be played again with [`ChessTurnEngine::redo_turn`].
     pub fn undo_turn(&mut self) -> Result<(), GameError> {
-        self.game.undo_turn()
+        self.game.takeback()
     }
 
-    /// Get list of available turns
-    pub fn available_turns(&self) -> &Vec<AvailableTurn> {
-        self.game.available_turns()
+    /// Play again the most recently undone turn.
+    ///
+    /// Undone turns are discarded once a turn is played in any other way.
+    /// Time spent on a timed turn and the clock are restored as well.
+    ///
+    /// # Return value
+    ///  - `Ok` - Gamestate after the turn was played again
+    ///  - `Err` - `GameError::RedoNotAvailable` if there's no undone turn
+    ///    which leads from the current position
+    pub fn redo_turn(&mut self) -> Result<Gamestate, GameError> {
+        self.game.redo_turn()
+    }
+
+    /// Undo or redo turns until exactly `ply` half moves are played.
+    ///
+    /// Useful to jump through the game, e.g. when the user clicks on a move
+    /// in the move list. Available turns are calculated just once, in the
+    /// target position.
+    ///
+    /// # Return value
+    ///  - `Ok` - Game is at the requested ply
+    ///  - `Err` - `GameError::RedoNotAvailable` if the requested ply is past
+    ///    the undone turns or those were discarded by playing another turn.
+    ///    Game stays at the last ply that could be reached.
+    pub fn goto_ply(&mut self, ply: usize) -> Result<(), GameError> {
+        self.game.goto_ply(ply)
+    }
+
+    /// Undo turn and get the info about the undone turn.
+    ///
+    /// Same as [`ChessTurnEngine::undo_turn`], but the undone turn is
+    /// returned, e.g. so the UI can animate the piece moving back.
+    ///
+    /// # Return value
+    ///  - `Ok` - Record of the undone turn along with its captured piece
+    ///  - `Err` - `GameError::UndoNotAvailable` if no turns were played
+    pub fn undo_turn_info(&mut self) -> Result<MoveRecord, GameError> {
+        self.game.undo_turn_info()
+    }
+
+    /// Undo turn and get the squares and pieces which the undo changed.
+    ///
+    /// Everything needed to animate the undo is returned: the moving piece
+    /// sliding back from [`UndoInfo::to`] to [`UndoInfo::from`] and the
+    /// captured piece reappearing on its square. Castling is described by
+    /// the king's move.
+    ///
+    /// # Return value
+    ///  - `Ok` - Details of the undone turn
+    ///  - `Err` - `GameError::UndoNotAvailable` if no turns were played
+    pub fn undo_turn_details(&mut self) -> Result<UndoInfo, GameError> {
+        self.game.undo_turn_details()
+    }
+
+    /// Create a new game with a mirrored position.
+    ///
+    /// Every piece moves to the same file on the opposite rank (e.g. `a2` ->
+    /// `a7`) and changes its color. The player on turn, castling rights and
+    /// the en-passant possibility are swapped as well. New game starts with
+    /// an empty turn history.
+    pub fn mirror_colors(&self) -> ChessTurnEngine {
+        ChessTurnEngine {
+            game: self.game.mirror_colors(),
+        }
+    }
+
+    /// Create a new game with the board rotated by 180 degrees.
+    ///
+    /// Every piece keeps its color and moves to the point-reflected square
+    /// (e.g. `a1` -> `h8`, `e2` -> `d7`). Since kings and rooks leave their
+    /// starting squares, castling rights are dropped along with the
+    /// en-passant possibility. New game starts with an empty turn history
+    /// and its gamestate is evaluated anew.
+    ///
+    /// # Return value
+    ///  - `Ok` - New engine with the rotated board
+    ///  - `Err` - Pawns keep moving in the same direction, so after the
+    ///    rotation they may attack the king of the player not on turn
+    pub fn rotate_180(&self) -> Result<ChessTurnEngine, &'static str> {
+        let game = self.game.rotate_180()?;
+        Ok(ChessTurnEngine { game })
+    }
+
+    /// Check whether the last turn was a two-square pawn move which allows
+    /// an en-passant capture.
+    pub fn en_passant_available(&self) -> bool {
+        self.game.en_passant_victim().is_some()
+    }
+
+    /// Get the square of the pawn which can be captured en-passant.
+    ///
+    /// # Return value
+    ///  - `Some` - Square of the pawn that made the two-square move
+    ///  - `None` - En-passant is not available
+    pub fn en_passant_victim(&self) -> Option<String> {
+        self.game
+            .en_passant_victim()
+            .map(|square| square.to_string())
+    }
+
+    /// Get the most recently played turn.
+    ///
+    /// Once the game is over, this is the turn which ended it.
+    ///
+    /// # Return value
+    ///  - `Some` - Turn written in chess notation format
+    ///  - `None` - No turns were played yet
+    pub fn last_move(&self) -> Option<String> {
+        self.game.last_move().map(String::from)
+    }
+
+    /// Get the turn which led to the current position.
+    ///
+    /// Same as [`ChessTurnEngine::last_move`], named for labeling positions
+    /// while stepping through a game.
+    pub fn move_leading_here(&self) -> Option<String> {
+        self.last_move()
+    }
+
+    /// Get the turn played at the given half move.
+    ///
+    /// # Return value
+    ///  - `Some` - Turn written in chess notation format
+    ///  - `None` - No turn was played at that half move yet
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - Half move index, `0` is the first turn of the game
+    pub fn move_at(&self, ply: usize) -> Option<String> {
+        self.game.move_at(ply).map(String::from)
+    }
+
+    /// Iterate over the played turns, starting with the first one.
+    ///
+    /// Undone turns are not part of the history.
+    pub fn history(&self) -> impl Iterator<Item = MoveRecord> + '_ {
+        self.game.history()
+    }
+
+    /// Iterate over the time spent on every played turn, starting with the
+    /// first one.
+    ///
+    /// Turns played with [`ChessTurnEngine::play_turn`] have no duration.
+    pub fn move_durations(
+        &self,
+    ) -> impl Iterator<Item = Option<Duration>> + '_ {
+        self.game.move_durations()
+    }
+
+    /// Save the current game state.
+    ///
+    /// Game can later jump back to this state with
+    /// [`ChessTurnEngine::restore`].
+    pub fn checkpoint(&self) -> GameCheckpoint {
+        GameCheckpoint {
+            game: self.game.clone(),
+        }
+    }
+
+    /// Restore the game state saved in the `checkpoint`.
+    ///
+    /// Board, turn history and the gamestate are restored along with the
+    /// available turns of the restored position.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint` - Previously saved game state
+    pub fn restore(&mut self, checkpoint: &GameCheckpoint) {
+        self.game = checkpoint.game.clone();
+    }
+
+    /// Get list of available turns
+    pub fn available_turns(&self) -> &Vec<AvailableTurn> {
+        self.game.available_turns()
+    }
+
+    /// Get list of turns the player not on turn could play if it were their
+    /// turn.
+    ///
+    /// Useful to see what the opponent is threatening. Player on turn stays
+    /// the same.
+    pub fn opponent_turns(&self) -> Vec<AvailableTurn> {
+        self.game.opponent_turns()
+    }
+
+    /// Get available turns labeled by the way they escape the check.
+    ///
+    /// Every legal turn in check is an evasion. It's labeled as a king move
+    /// for any turn of the king, as a capture if it takes the checking piece
+    /// and as a block otherwise.
+    ///
+    /// # Return value
+    /// Empty if the player on turn is not in check
+    pub fn check_evasions(&self) -> Vec<(CheckEvasionKind, &AvailableTurn)> {
+        self.game.check_evasions()
+    }
+
+    /// Iterate over the available turns.
+    ///
+    /// Same turns as [`ChessTurnEngine::available_turns`] without exposing
+    /// the underlying vector.
+    pub fn iter_available_turns(&self) -> impl Iterator<Item = &AvailableTurn> {
+        self.game.available_turns().iter()
+    }
+
+    /// Generate the available turns lazily, one piece at a time.
+    ///
+    /// Turns come in the same order as from
+    /// [`ChessTurnEngine::available_turns`], but the turns of a piece are
+    /// calculated only once the iterator gets to it. Search which stops
+    /// early, e.g. at the first capture, skips the rest of the work.
+    /// Generated turns are not cached.
+    pub fn lazy_available_turns(
+        &self,
+    ) -> impl Iterator<Item = AvailableTurn> + '_ {
+        self.game
+            .generate_turns()
+            .map(|turn| AvailableTurn::from(&turn))
+    }
+
+    /// Get list of available turns which capture a piece.
+    ///
+    /// En-passant captures are included as well.
+    pub fn capture_turns(&self) -> Vec<&AvailableTurn> {
+        self.game.capture_turns()
+    }
+
+    /// Get available turns grouped by the moving piece, e.g. for a move
+    /// list panel.
+    ///
+    /// # Return value
+    /// Turns of every piece type sorted by their notation, keyed by the
+    /// piece name: e.g. `Knight`. Castling is listed under the `King`.
+    pub fn available_turns_grouped(
+        &self,
+    ) -> BTreeMap<String, Vec<&AvailableTurn>> {
+        self.game.available_turns_grouped()
+    }
+
+    /// Get list of available turns which promote a pawn.
+    ///
+    /// Every piece the pawn can be promoted to is a turn of its own, and
+    /// promotions with a capture are included as well.
+    pub fn promotion_turns(&self) -> Vec<&AvailableTurn> {
+        self.game.promotion_turns()
+    }
+
+    /// Get list of available turns which give a check or a checkmate.
+    ///
+    /// Castling and promotion turns are included when they give a check.
+    pub fn checking_turns(&self) -> Vec<&AvailableTurn> {
+        self.game.checking_turns()
+    }
+
+    /// Get list of available turns which stalemate the opponent.
+    ///
+    /// Opposite of a mate in one: the opponent is left without any legal
+    /// turn while not being in check. Handy for spotting stalemate traps
+    /// in endgame studies.
+    pub fn stalemate_in_one(&self) -> Vec<&AvailableTurn> {
+        self.game.stalemate_in_one()
+    }
+
+    /// Get list of available turns which give a discovered check.
+    ///
+    /// The check comes from a piece other than the moving one, whose line to
+    /// the enemy king got uncovered. Double checks are included as well,
+    /// while castling is not, since the rook always gives the check.
+    pub fn discovered_check_moves(&self) -> Vec<&AvailableTurn> {
+        self.game.discovered_check_moves()
+    }
+
+    /// Get squares where the pawn can be promoted along with the pieces it
+    /// can be promoted to.
+    ///
+    /// Result is empty for invalid squares and for pieces which cannot be
+    /// promoted with the next turn.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - Square of the pawn, e.g. `b7`
+    pub fn promotion_options(&self, src: &str) -> Vec<(String, Vec<String>)> {
+        let src = match Square::try_from(src) {
+            Ok(src) => src,
+            Err(_) => return vec![],
+        };
+
+        self.game
+            .promotion_options(src)
+            .into_iter()
+            .map(|(dst, pieces)| {
+                let pieces = pieces.iter().map(|p| p.to_string()).collect();
+                (dst.to_string(), pieces)
+            })
+            .collect()
+    }
+
+    /// Check whether the player on turn has exactly one legal turn.
+    ///
+    /// Finished games have no turns, so they are never forced.
+    pub fn is_forced(&self) -> bool {
+        self.game.is_forced()
+    }
+
+    /// Get the only legal turn of the player on turn.
+    ///
+    /// # Return value
+    ///  - `Some` - Turn in case it's the only one available
+    ///  - `None` - Game is over or more turns are available
+    pub fn only_move(&self) -> Option<&AvailableTurn> {
+        self.game.only_move()
+    }
+
+    /// Get the piece placed on the given square.
+    ///
+    /// # Return value
+    ///  - `Some` - Piece name and its side, e.g. `("Knight", "White")`
+    ///  - `None` - Square is empty or it's not a valid square
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - Square written with lowercase letters: e.g. `e4`
+    pub fn piece_at(&self, square: &str) -> Option<(String, String)> {
+        let square = Square::try_from(square).ok()?;
+
+        self.game
+            .piece_at(square)
+            .map(|(piece, side)| (piece.to_string(), side.to_string()))
+    }
+
+    /// Get a snapshot of the board as 64 encoded squares, e.g. for FFI.
+    ///
+    /// Squares are ordered the same way as the indexes explained in
+    /// [`square_name`], from `a8` (0) to `h1` (63). Empty squares are `0`,
+    /// otherwise the byte is laid out as:
+    ///  - bits 0-2: piece - `1` Pawn, `2` Rook, `3` Knight, `4` Bishop,
+    ///    `5` Queen, `6` King
+    ///  - bit 6: Black piece
+    ///  - bit 7: White piece
+    ///
+    /// E.g. the white king is `0x86` and the black pawn is `0x41`.
+    pub fn board_bytes(&self) -> [u8; 64] {
+        self.game.board_bytes()
+    }
+
+    /// Same as [`ChessTurnEngine::piece_at`], but the square is given as an
+    /// index.
+    ///
+    /// Index convention is explained in [`square_name`].
+    ///
+    /// # Arguments
+    ///
+    /// * `idx` - Square index from 0 to 63
+    pub fn piece_at_index(&self, idx: u8) -> Option<(String, String)> {
+        let square = game::index_to_square(idx)?;
+
+        self.game
+            .piece_at(square)
+            .map(|(piece, side)| (piece.to_string(), side.to_string()))
+    }
+
+    /// Typed variant of [`ChessTurnEngine::piece_at`].
+    ///
+    /// # Return value
+    ///  - `Some` - Piece and its side
+    ///  - `None` - Square is empty
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - Square on the board
+    pub fn piece_at_typed(&self, square: Square) -> Option<(Piece, Side)> {
+        self.game.piece_at(square)
+    }
+
+    /// Get all pieces of a given side along with their squares.
+    ///
+    /// Pieces are listed rank by rank from `a8` to `h1`. Result is empty if
+    /// `side` is not recognized.
+    ///
+    /// # Return value
+    /// `(square, piece)` pairs, e.g. `("e1", "King")`
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn pieces_of(&self, side: &str) -> Vec<(String, String)> {
+        let side = match parse_side(side) {
+            Some(side) => side,
+            None => return vec![],
+        };
+
+        self.game
+            .pieces_of(side)
+            .iter()
+            .map(|(square, piece)| (square.to_string(), piece.to_string()))
+            .collect()
+    }
+
+    /// Get rules the game is played by.
+    pub fn variant(&self) -> GameVariant {
+        self.game.variant()
+    }
+
+    /// Get pieces in the pocket of a given side, available for drops.
+    ///
+    /// Pocket is always empty unless the game is played as
+    /// [`GameVariant::Crazyhouse`]. Result is empty if `side` is not
+    /// recognized.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn pocket(&self, side: &str) -> Vec<String> {
+        let side = match parse_side(side) {
+            Some(side) => side,
+            None => return vec![],
+        };
+
+        self.game
+            .pocket(side)
+            .iter()
+            .map(|piece| piece.to_string())
+            .collect()
+    }
+
+    /// Count pieces of a given type for a given side.
+    ///
+    /// Returns zero if either `side` or `piece` is not recognized.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    /// * `piece` - Piece letter as in the custom setup or the full name:
+    ///   e.g. `N` or `Knight`
+    pub fn piece_count(&self, side: &str, piece: &str) -> usize {
+        match (parse_side(side), parse_piece(piece)) {
+            (Some(side), Some(piece)) => self.game.piece_count(side, piece),
+            _ => 0,
+        }
+    }
+
+    /// Check whether the side has enough pieces to deliver a checkmate.
+    ///
+    /// A bare king and a king with a single knight or bishop are not enough.
+    /// Losing on time against such a side ends the game in a draw.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn has_mating_material(&self, side: &str) -> bool {
+        parse_side(side).is_some_and(|side| self.game.has_mating_material(side))
+    }
+
+    /// Get the material signature of the position, e.g. `KRPvKR`.
+    ///
+    /// White's pieces come first, followed by `v` and Black's pieces, each
+    /// ordered as K, Q, R, B, N, P. It's the key which endgame tablebases
+    /// and classifiers are indexed by.
+    pub fn material_signature(&self) -> String {
+        self.game.material_signature()
+    }
+
+    /// Check whether the position is a known theoretical draw.
+    ///
+    /// Neither player can deliver a checkmate in these cases:
+    ///  - K vs K
+    ///  - K+N vs K and K+B vs K
+    ///  - K+B vs K+B with the bishops on the same color
+    ///  - K+N+N vs K
+    ///
+    /// Unlike the insufficient mating material rule, which ends the game, it
+    /// doesn't depend on whose turn it is. Any pawn, rook or queen on the
+    /// board means the position is not a theoretical draw.
+    pub fn is_theoretical_draw(&self) -> bool {
+        self.game.is_theoretical_draw()
+    }
+
+    /// Classify every file by the pawns standing on it.
+    ///
+    /// Useful for positional evaluation, e.g. to reward rooks on open files.
+    ///
+    /// # Return value
+    /// Status of the files indexed from `a` (0) to `h` (7)
+    pub fn file_status(&self) -> [FileStatus; 8] {
+        self.game.file_status()
+    }
+
+    /// Get squares of the passed pawns of a given side.
+    ///
+    /// Pawn is passed when no enemy pawn stands ahead of it on the same file
+    /// or any of the neighboring files. Result is empty if `side` is not
+    /// recognized.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn passed_pawns(&self, side: &str) -> Vec<String> {
+        let side = match parse_side(side) {
+            Some(side) => side,
+            None => return vec![],
+        };
+
+        self.game
+            .passed_pawns(side)
+            .iter()
+            .map(|square| square.to_string())
+            .collect()
+    }
+
+    /// Get weak pawns of a given side: doubled and isolated ones.
+    ///
+    /// Every weak pawn is listed with its square, so the UI can highlight
+    /// it. Pawn may be both doubled and isolated. Result is empty if `side`
+    /// is not recognized.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn pawn_structure(&self, side: &str) -> PawnStructure {
+        match parse_side(side) {
+            Some(side) => self.game.pawn_structure(side),
+            None => PawnStructure::default(),
+        }
+    }
+
+    /// Check whether no sequence of turns can ever lead to a checkmate due
+    /// to a pawn fortress.
+    ///
+    /// Only kings and pawns may be on the board, every pawn must be blocked
+    /// by another pawn with nothing to capture and no king may reach an
+    /// enemy pawn which isn't protected by another pawn. The check is
+    /// conservative: some dead positions are not detected, but a position
+    /// reported as dead is always dead. Always `false` in Crazyhouse.
+    pub fn is_dead_position(&self) -> bool {
+        self.game.is_dead_position()
+    }
+
+    /// Get FEN record of the current position.
+    ///
+    /// Full move number continues from the record the game started from
+    /// and grows after every turn played by Black.
+    pub fn fen(&self) -> String {
+        self.game.fen()
+    }
+
+    /// Get every available turn paired with the FEN record of the position
+    /// it leads to, e.g. for a one-ply search or an opening tree.
+    ///
+    /// Turns are listed in the same order as
+    /// [`ChessTurnEngine::available_turns`]. The game itself is left intact.
+    ///
+    /// # Return value
+    ///  - `(turn, fen)` - Turn in chess notation format and the FEN record
+    ///    after it's played
+    pub fn successors(&self) -> Vec<(String, String)> {
+        self.game.successors()
+    }
+
+    /// Get FEN record of the position the game started from.
+    ///
+    /// Played turns don't change it, e.g. for [`Setup::Normal`] it's always
+    /// `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1`.
+    pub fn setup_fen(&self) -> String {
+        self.game.setup_fen().to_string()
+    }
+
+    /// Get piece placement of the board, i.e. the first field of the FEN
+    /// record.
+    ///
+    /// Ranks are listed from the 8th to the 1st one and separated by `/`,
+    /// e.g. `rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR`.
+    pub fn fen_placement(&self) -> String {
+        self.game.fen_placement()
+    }
+
+    /// Check whether the other game has reached the same position.
+    ///
+    /// Turn history is ignored, while pieces on the board, the player on
+    /// turn, castling rights and the en-passant possibility must match.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Game to compare with
+    pub fn same_position(&self, other: &ChessTurnEngine) -> bool {
+        self.game.same_position(&other.game)
+    }
+
+    /// Check whether the player can castle with the next turn.
+    ///
+    /// Unlike castling rights, this takes the current position into account:
+    /// squares between the king and the rook must be empty and the king must
+    /// not be in check nor cross an attacked square. Player who is not on
+    /// turn cannot castle.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    /// * `kind` - `short`/`long` or the notation: e.g. `0-0-0`
+    pub fn can_castle(&self, side: &str, kind: &str) -> bool {
+        match (parse_side(side), parse_castling_type(kind)) {
+            (Some(side), Some(kind)) => self.game.can_castle(side, kind),
+            _ => false,
+        }
+    }
+
+    /// Get home square of the rook which takes part in the castling.
+    ///
+    /// # Return value
+    ///  - `Some` - Square of the rook, e.g. `h1` for White's short castling
+    ///  - `None` - Castling right is gone or the input is not recognized
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    /// * `kind` - `short`/`long` or the notation: e.g. `0-0-0`
+    pub fn castling_rook_square(
+        &self,
+        side: &str,
+        kind: &str,
+    ) -> Option<String> {
+        let side = parse_side(side)?;
+        let kind = parse_castling_type(kind)?;
+
+        self.game
+            .castling_rook_square(side, kind)
+            .map(|square| square.to_string())
+    }
+
+    /// Count white and black pieces attacking every square, e.g. for a
+    /// heatmap of the contested squares.
+    ///
+    /// Entry `[row][file]` holds `(white_attackers, black_attackers)`, where
+    /// the row `0` is the rank 8 and the file `0` is the a-file, so
+    /// `control_map()[7][4]` belongs to `e1`. Pinned pieces count as
+    /// attackers, as in [`ChessTurnEngine::attackers_of`], and so do pieces
+    /// defending an ally on the square.
+    pub fn control_map(&self) -> [[(u8, u8); 8]; 8] {
+        self.game.control_map()
+    }
+
+    /// Get squares of the `attacker_side` pieces which attack the given
+    /// square.
+    ///
+    /// Pinned pieces are included, since they still attack the square. Result
+    /// is empty for invalid squares or an unrecognized side.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - Attacked square, e.g. `d5`
+    /// * `attacker_side` - `w`/`b` or the full name: e.g. `White`
+    pub fn attackers_of(
+        &self,
+        square: &str,
+        attacker_side: &str,
+    ) -> Vec<String> {
+        let (square, side) =
+            match (Square::try_from(square), parse_side(attacker_side)) {
+                (Ok(square), Some(side)) => (square, side),
+                _ => return vec![],
+            };
+
+        self.game
+            .attackers_of(square, side)
+            .iter()
+            .map(|square| square.to_string())
+            .collect()
+    }
+
+    /// Get squares of the pieces which defend the piece on the given square.
+    ///
+    /// Defenders are pieces of the same color which attack the square. Result
+    /// is empty for invalid or empty squares.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - Square of the defended piece, e.g. `d5`
+    pub fn defenders_of(&self, square: &str) -> Vec<String> {
+        let square = match Square::try_from(square) {
+            Ok(square) => square,
+            Err(_) => return vec![],
+        };
+
+        self.game
+            .defenders_of(square)
+            .iter()
+            .map(|square| square.to_string())
+            .collect()
+    }
+
+    /// Get squares of the pieces defended by the piece on the given square.
+    ///
+    /// Defended pieces belong to the same player and are attacked by the
+    /// defending piece, e.g. pawn defends pieces diagonally in front of it.
+    /// Result is empty for invalid or empty squares, an unrecognized side and
+    /// for pieces which don't belong to the `side`.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - Square of the defending piece, e.g. `e4`
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn defended_squares(&self, square: &str, side: &str) -> Vec<String> {
+        let (square, side) = match (Square::try_from(square), parse_side(side))
+        {
+            (Ok(square), Some(side)) => (square, side),
+            _ => return vec![],
+        };
+
+        self.game
+            .defended_squares(square, side)
+            .iter()
+            .map(|square| square.to_string())
+            .collect()
+    }
+
+    /// Get the square of the player's king.
+    ///
+    /// # Return value
+    ///  - `Some` - Square of the king, e.g. `e1`
+    ///  - `None` - `side` is not recognized
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - `w`/`b` or the full name: e.g. `White`
+    pub fn king_square(&self, side: &str) -> Option<String> {
+        parse_side(side).map(|side| self.game.king_square(side).to_string())
+    }
+
+    /// Count all pieces on the board, kings included
+    pub fn total_pieces(&self) -> usize {
+        self.game.total_pieces()
+    }
+
+    /// Count pieces of both sides on the board, kings included
+    ///
+    /// # Return value
+    ///  - `(white, black)` - Number of white and black pieces
+    pub fn piece_counts(&self) -> (usize, usize) {
+        self.game.piece_counts()
+    }
+
+    /// Get list of available turns without building any strings.
+    ///
+    /// Cheaper alternative to [`ChessTurnEngine::available_turns`] for
+    /// callers that only need the count of turns or just a few of them.
+    pub fn available_turns_raw(&self) -> &Vec<RawTurn> {
+        self.game.available_turns_raw()
+    }
+
+    /// Get list of available turns in the long algebraic format used by UCI.
+    ///
+    /// Turns are written as source and destination squares, e.g. `e2e4`.
+    /// Castling is written as the king's move, e.g. `e1g1`, and promotions
+    /// end with the lowercase letter of the new piece, e.g. `e7e8q`.
+    pub fn available_moves_uci(&self) -> Vec<String> {
+        self.game.available_moves_uci()
+    }
+
+    /// Search for a forced mate for the player on turn.
+    ///
+    /// Turns are played and undone during the search, so the game is left
+    /// unchanged once the search is over.
+    ///
+    /// # Return value
+    ///  - `Some` - Mating line written in chess notation format, starting
+    ///    with the turn of the player on turn.
+    ///  - `None` - No forced mate found within `max_depth` full moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth` - Maximum number of full moves the mate may take
+    pub fn find_mate(&mut self, max_depth: usize) -> Option<Vec<String>> {
+        self.game.find_mate(max_depth)
+    }
+
+    /// Evaluate the current position.
+    ///
+    /// Score is based on the material on the board and the mobility of both
+    /// players. It's expressed in centipawns from White's point of view, so a
+    /// positive score favors White and a negative score favors Black.
+    ///
+    /// # Return value
+    ///  - Positive or negative 100000 for a won game
+    ///  - Zero for a drawn game
+    pub fn evaluate(&self) -> i32 {
+        self.game.evaluate()
+    }
+
+    /// Suggest the best turn for the player on turn.
+    ///
+    /// Available turns are explored with a negamax search and the leaf
+    /// positions are scored with [`ChessTurnEngine::evaluate`]. Turns are
+    /// played and undone during the search, so the game is left unchanged
+    /// once the search is over.
+    ///
+    /// # Return value
+    ///  - `Some` - Suggested turn written in chess notation format
+    ///  - `None` - Game is over
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of half moves to look ahead
+    pub fn suggest_move(&mut self, depth: usize) -> Option<String> {
+        self.game.suggest_move(depth)
+    }
+
+    /// Get a turn which doesn't lose right away, e.g. as a hint for
+    /// beginners.
+    ///
+    /// Every candidate turn is checked against the opponent's replies. The
+    /// turn must not allow a mate in one and must not let the opponent win
+    /// material by a capture, while a capture of a defended piece is expected
+    /// to be recaptured. The first such turn is returned. In case every turn
+    /// loses material, the one losing the least is returned.
+    ///
+    /// # Return value
+    ///  - `Some` - Hinted turn
+    ///  - `None` - Game is over
+    pub fn safe_hint(&self) -> Option<&AvailableTurn> {
+        self.game.safe_hint()
+    }
+
+    /// Count all positions reachable in exactly `depth` half moves.
+    ///
+    /// Useful for verifying the correctness of the turn generation. Turns are
+    /// played and undone, so the game is left unchanged once the counting is
+    /// over. Branches end early once the game is over.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of half moves to count
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        self.game.perft(depth)
+    }
+
+    /// Same as [`ChessTurnEngine::perft`], but the positions are counted
+    /// separately for every root turn.
+    ///
+    /// Comparing the counts against another engine helps to find the turn
+    /// whose subtree is generated incorrectly.
+    ///
+    /// # Return value
+    /// Root turns written in chess notation format along with the number of
+    /// positions reachable after them. Counts sum up to `perft(depth)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of half moves to count, root turn included
+    pub fn perft_divide(&mut self, depth: usize) -> Vec<(String, u64)> {
+        self.game.perft_divide(depth)
+    }
+
+    /// Same as [`ChessTurnEngine::perft`], but every root turn is counted on
+    /// its own copy of the game in a `rayon` thread pool.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of half moves to count
+    #[cfg(feature = "rayon")]
+    pub fn perft_parallel(&self, depth: usize) -> u64 {
+        self.game.perft_parallel(depth)
+    }
+}
+
+/// Get available turns and the game status of a position given as a FEN
+/// record, without keeping the engine around.
+///
+/// Positions which are already over, e.g. by a checkmate, a stalemate or
+/// insufficient mating material, have no available turns.
+///
+/// # Return value
+///  - `Ok` - Available turns along with the game status
+///  - `Err` - Description of the problem with the FEN record
+///
+/// # Arguments
+///
+/// * `fen` - Position in FEN format, e.g.
+///   `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1`
+pub fn turns_and_state_from_fen(
+    fen: &str,
+) -> Result<(Vec<AvailableTurn>, Gamestate), String> {
+    let game = Game::from_fen(fen).map_err(|e| e.to_string())?;
+    Ok((game.available_turns().clone(), game.gamestate()))
+}
+
+/// Get name of the square with the given index
+///
+/// Squares are indexed from the top left corner of the board as seen from the
+/// White's perspective: `a8` is 0, `h8` is 7, `a1` is 56 and `h1` is 63.
+///
+/// # Return value
+///  - `Some` - Square name: e.g. `e4`
+///  - `None` - Index is larger than 63
+pub fn square_name(idx: u8) -> Option<String> {
+    game::index_to_square(idx).map(|square| square.to_string())
+}
+
+/// Get zero-based coordinates of the square
+///
+/// File `a` and the 1st rank are both 0, while file `h` and the 8th rank are
+/// both 7.
+///
+/// # Return value
+///  - `Some` - `(file, rank)` coordinates: e.g. `(4, 3)` for `e4`
+///  - `None` - Square name is not valid
+///
+/// # Arguments
+///
+/// * `square` - Square name: e.g. `e4`
+pub fn square_to_coords(square: &str) -> Option<(u8, u8)> {
+    Square::try_from(square).ok().map(game::square_to_coords)
+}
+
+/// Get name of the square with the given zero-based coordinates
+///
+/// # Return value
+///  - `Some` - Square name: e.g. `e4` for `(4, 3)`
+///  - `None` - Any of the coordinates is larger than 7
+///
+/// # Arguments
+///
+/// * `file` - File index, where file `a` is 0
+/// * `rank` - Rank index, where the 1st rank is 0
+pub fn coords_to_square(file: u8, rank: u8) -> Option<String> {
+    game::coords_to_square(file, rank).map(|square| square.to_string())
+}
+
+/// Get names of the squares lying on the line between two squares
+///
+/// Useful to find the squares where a check can be blocked or a pinned piece
+/// can move. Neither of the two squares is included.
+///
+/// # Return value
+/// Squares ordered from `a` to `b`, e.g. `["d2", "e3"]` for `c1` and `f4`.
+/// Empty in case the squares don't share a rank, a file or a diagonal, or any
+/// of the square names is not valid.
+///
+/// # Arguments
+///
+/// * `a` - Square name: e.g. `c1`
+/// * `b` - Square name: e.g. `f4`
+pub fn squares_between(a: &str, b: &str) -> Vec<String> {
+    match (Square::try_from(a), Square::try_from(b)) {
+        (Ok(a), Ok(b)) => game::squares_between(a, b)
+            .iter()
+            .map(|square| square.to_string())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Validate a position written in the FEN format without creating a game
+///
+/// All six FEN fields are checked: piece placement, side to move, castling
+/// availability, en-passant target square, halfmove clock and fullmove
+/// number.
+///
+/// # Return value
+///  - `Ok` - Valid FEN record
+///  - `Err` - Description of the first found problem
+///
+/// # Arguments
+///
+/// * `fen` - Position in FEN format, e.g. default setup is
+///   `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1`
+pub fn validate_fen(fen: &str) -> Result<(), String> {
+    game::validate_fen(fen)
+}
+
+/// Parse side given either as a letter or as a full name
+fn parse_side(side: &str) -> Option<Side> {
+    match side {
+        "White" | "white" => Some(Side::White),
+        "Black" | "black" => Some(Side::Black),
+        _ => Side::try_from(side).ok(),
+    }
+}
+
+/// Parse castling type given either as a name or as the notation
+fn parse_castling_type(kind: &str) -> Option<CastlingType> {
+    match kind {
+        "short" | "Short" | "0-0" | "O-O" => Some(CastlingType::Short),
+        "long" | "Long" | "0-0-0" | "O-O-O" => Some(CastlingType::Long),
+        _ => None,
+    }
+}
+
+/// Parse piece given either as a letter or as a full name
+fn parse_piece(piece: &str) -> Option<Piece> {
+    match piece {
+        "Pawn" | "pawn" => Some(Piece::Pawn),
+        "Rook" | "rook" => Some(Piece::Rook),
+        "Knight" | "knight" => Some(Piece::Knight),
+        "Bishop" | "bishop" => Some(Piece::Bishop),
+        "Queen" | "queen" => Some(Piece::Queen),
+        "King" | "king" => Some(Piece::King),
+        _ => Piece::try_from(piece).ok(),
+    }
+}
+
+/// Everything a square can hold, since indexing must return a reference
+const SQUARE_CONTENTS: [Option<(Piece, Side)>; 13] = [
+    None,
+    Some((Piece::Pawn, Side::White)),
+    Some((Piece::Rook, Side::White)),
+    Some((Piece::Knight, Side::White)),
+    Some((Piece::Bishop, Side::White)),
+    Some((Piece::Queen, Side::White)),
+    Some((Piece::King, Side::White)),
+    Some((Piece::Pawn, Side::Black)),
+    Some((Piece::Rook, Side::Black)),
+    Some((Piece::Knight, Side::Black)),
+    Some((Piece::Bishop, Side::Black)),
+    Some((Piece::Queen, Side::Black)),
+    Some((Piece::King, Side::Black)),
+];
+
+/// Access the piece placed on the given square: e.g. `cte["e4"]`
+///
+/// Same as [`ChessTurnEngine::piece_at_typed`], except that it panics on
+/// invalid squares.
+impl Index<&str> for ChessTurnEngine {
+    type Output = Option<(Piece, Side)>;
+
+    fn index(&self, square: &str) -> &Self::Output {
+        let content = match Square::try_from(square) {
+            Ok(square) => self.game.piece_at(square),
+            Err(_) => panic!("Invalid square: {}", square),
+        };
+
+        SQUARE_CONTENTS
+            .iter()
+            .find(|c| **c == content)
+            .expect("Every square content is listed")
     }
 }
@@ -9,6 +9,7 @@ fn normal_setup_valid() {
     // Make sure turn and capture histry are empty
     assert_eq!(cte.display(DisplayOption::TurnHistory), String::new());
     assert_eq!(cte.display(DisplayOption::CaptureHistory), String::new());
+    assert_eq!(cte.display(DisplayOption::Movetext), String::new());
 
     cte.display_on_screen(DisplayOption::BoardView(ViewMode::FancyTui));
     cte.display_on_screen(DisplayOption::BoardView(ViewMode::SimpleAscii));
@@ -24,6 +25,39 @@ fn normal_setup_valid() {
     );
 }
 
+#[test]
+fn opponent_turns() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.opponent_turns().len(), 20);
+
+    play(&mut cte, "e4 e5 Bc4");
+    let threats = cte.opponent_turns();
+    assert!(threats.iter().any(|turn| turn.get_turn() == "Bxf7+"));
+    assert!(threats.iter().all(|turn| turn.src != "e7"));
+
+    // Player on turn is unchanged
+    assert!(cte.available_turns().iter().any(|turn| turn.src == "g8"));
+    assert!(cte.play_turn("Nc6").is_ok());
+}
+
+#[test]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<ChessTurnEngine>();
+    assert_send_sync::<GameCheckpoint>();
+    assert_send_sync::<AvailableTurn>();
+    assert_send_sync::<RawTurn>();
+    assert_send_sync::<GameError>();
+
+    let cte = std::sync::Arc::new(ChessTurnEngine::new(Setup::Normal).unwrap());
+    let shared = std::sync::Arc::clone(&cte);
+    let count = std::thread::spawn(move || shared.available_turns().len())
+        .join()
+        .unwrap();
+    assert_eq!(count, 20);
+}
+
 #[test]
 fn custom_setup_valid() {
     const NO_PAWNS: &'static str = "
@@ -104,6 +138,17 @@ fn custom_setup_with_invalid_number_of_kings() {
     assert!(ChessTurnEngine::new(Setup::Custom(NO_KINGS)).is_err());
 }
 
+#[test]
+fn custom_setup_with_idle_king_in_check() {
+    // White is on turn while the black king is attacked by the rook
+    const KING_IN_CHECK: &str = "a8,w,R e1,w,K e8,b,K";
+    assert!(ChessTurnEngine::new(Setup::Custom(KING_IN_CHECK)).is_err());
+
+    // Player on turn may be in check
+    const OUR_KING_IN_CHECK: &str = "a1,b,R e1,w,K e8,b,K";
+    assert!(ChessTurnEngine::new(Setup::Custom(OUR_KING_IN_CHECK)).is_ok());
+}
+
 #[test]
 fn cannot_undo_before_the_first_turn_is_played() {
     let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
@@ -128,6 +173,95 @@ fn custom_setup_with_multiple_knights() {
     play(&mut cte, "Nb1d2"); // Here it works!
 }
 
+#[test]
+// Three queens can reach e1, so the queen from h4 shares both its file and
+// its rank with other queens and needs the whole square in the notation
+fn custom_setup_with_full_square_disambiguation() {
+    const QUEENS: &str = "h4,w,Q h1,w,Q e4,w,Q a1,w,K b6,b,K";
+
+    let mut cte = ChessTurnEngine::new(Setup::Custom(QUEENS)).unwrap();
+    for turn in ["Qh4e1", "Q1e1", "Qee1"] {
+        assert!(
+            available_turns_contain_turn(cte.available_turns(), turn),
+            "Missing turn {}",
+            turn
+        );
+    }
+
+    // Disambiguation is provided on its own as well
+    let disambiguation = |src: &str| {
+        cte.available_turns()
+            .iter()
+            .find(|turn| turn.src == src && turn.dst == "e1")
+            .and_then(|turn| turn.disambiguation.clone())
+    };
+    assert_eq!(disambiguation("h4").as_deref(), Some("h4"));
+    assert_eq!(disambiguation("h1").as_deref(), Some("1"));
+    assert_eq!(disambiguation("e4").as_deref(), Some("e"));
+    assert!(cte
+        .available_turns()
+        .iter()
+        .filter(|turn| turn.piece == "King")
+        .all(|turn| turn.disambiguation.is_none()));
+
+    play(&mut cte, "Qh4e1");
+    assert_eq!(cte.piece_at("e1"), Some(("Queen".into(), "White".into())));
+    assert_eq!(cte.piece_at("h4"), None);
+}
+
+#[test]
+fn can_castle() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    // Rights are present, but squares are occupied
+    assert!(!cte.can_castle("White", "short"));
+
+    play(&mut cte, "e4 e5 Nf3 Nc6 Bc4");
+    // Black is on turn
+    assert!(!cte.can_castle("White", "short"));
+
+    play(&mut cte, "Bc5");
+    assert!(cte.can_castle("White", "short"));
+    assert!(cte.can_castle("w", "0-0"));
+    assert!(!cte.can_castle("White", "long"));
+    assert!(!cte.can_castle("White", "sideways"));
+    assert!(!cte.can_castle("Purple", "short"));
+
+    // King would cross the attacked f1 square
+    let cte =
+        ChessTurnEngine::from_fen("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1")
+            .unwrap();
+    assert!(!cte.can_castle("White", "short"));
+    assert!(cte.can_castle("White", "long"));
+
+    // King is in check
+    let cte =
+        ChessTurnEngine::from_fen("r3k2r/8/8/8/8/8/4r3/R3K2R w KQkq - 0 1")
+            .unwrap();
+    assert!(!cte.can_castle("White", "short"));
+    assert!(!cte.can_castle("White", "long"));
+}
+
+#[test]
+fn castling_rook_square() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let rook = |cte: &ChessTurnEngine, side, kind| {
+        cte.castling_rook_square(side, kind)
+    };
+
+    assert_eq!(rook(&cte, "w", "short"), Some("h1".to_string()));
+    assert_eq!(rook(&cte, "White", "0-0-0"), Some("a1".to_string()));
+    assert_eq!(rook(&cte, "b", "O-O"), Some("h8".to_string()));
+    assert_eq!(rook(&cte, "black", "long"), Some("a8".to_string()));
+    assert_eq!(rook(&cte, "x", "long"), None);
+    assert_eq!(rook(&cte, "w", "sideways"), None);
+
+    // Moving the rook drops the right
+    play(&mut cte, "a4 h5 Ra3 Rh6");
+    assert_eq!(rook(&cte, "w", "long"), None);
+    assert_eq!(rook(&cte, "b", "short"), None);
+    assert_eq!(rook(&cte, "w", "short"), Some("h1".to_string()));
+}
+
 #[test]
 fn test_simulated_castlings1() {
     let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
@@ -165,6 +299,65 @@ fn test_simulated_castlings2() {
     invalid_turn(&mut cte, "0-0-0", GameError::CastlingUnavailable);
 }
 
+#[test]
+fn move_bytes_round_trip() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.to_move_bytes().unwrap().is_empty());
+
+    // Game with en-passant, promotion and castling on both sides
+    assert!(cte
+        .play_sequence(
+            "1. e4 d5 2. e5 f5 3. exf6 Nc6 4. fxg7 Bd7 5. gxh8=N e6 \
+             6. Nf3 Qe7 7. Bb5 0-0-0 8. 0-0 d4 9. c4 dxc3 10. bxc3"
+        )
+        .is_ok());
+
+    let bytes = cte.to_move_bytes().unwrap();
+    assert_eq!(bytes.len(), 2 * 19);
+
+    let replayed = ChessTurnEngine::from_move_bytes(&bytes).unwrap();
+    assert!(replayed.same_position(&cte));
+    assert!(replayed
+        .history()
+        .map(|r| r.turn)
+        .eq(cte.history().map(|r| r.turn)));
+    assert_eq!(replayed.to_move_bytes().unwrap(), bytes);
+
+    // Odd length and illegal turns
+    assert_eq!(
+        ChessTurnEngine::from_move_bytes(&bytes[..3]).err(),
+        Some(GameError::ParsingTurnFailed)
+    );
+    assert_eq!(
+        ChessTurnEngine::from_move_bytes(&[0, 0]).err(),
+        Some(GameError::ParsingTurnFailed)
+    );
+
+    // Setup and variant are not encoded
+    let cte = ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K")).unwrap();
+    assert_eq!(
+        cte.to_move_bytes().err(),
+        Some(GameError::UnsupportedMoveBytesSetup)
+    );
+
+    let cte = ChessTurnEngine::from_fen(
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    )
+    .unwrap();
+    assert_eq!(
+        cte.to_move_bytes().err(),
+        Some(GameError::UnsupportedMoveBytesSetup)
+    );
+
+    let cte =
+        ChessTurnEngine::with_variant(Setup::Normal, GameVariant::Crazyhouse)
+            .unwrap();
+    assert_eq!(
+        cte.to_move_bytes().err(),
+        Some(GameError::UnsupportedMoveBytesSetup)
+    );
+}
+
 #[test]
 fn game_testing_errors1() {
     let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
@@ -174,7 +367,7 @@ fn game_testing_errors1() {
     play(&mut cte, "d3 e6  d4 e5  dxe5  f6 c4  d6 c5  fxe5");
 
     invalid_turn(&mut cte, "e7", GameError::MovingPieceNotFound);
-    invalid_turn(&mut cte, "a5", GameError::MovingPieceNotFound);
+    invalid_turn(&mut cte, "a5", GameError::NotYourPiece);
     invalid_turn(&mut cte, "ef3", GameError::InvalidPawnMovement);
     invalid_turn(&mut cte, "exf3", GameError::NoCapturePiece);
     invalid_turn(&mut cte, "Nb3", GameError::MovingPieceNotFound);
@@ -185,8 +378,8 @@ fn game_testing_errors1() {
     play(&mut cte, "Nxb5 Qa4  Nf3 Qxa3  Rxa3 Bf5");
 
     invalid_turn(&mut cte, "f7", GameError::MovingPieceNotFound);
-    invalid_turn(&mut cte, "Bfg7", GameError::MovingPieceNotFound);
-    invalid_turn(&mut cte, "Ra7", GameError::MovingPieceNotFound);
+    invalid_turn(&mut cte, "Bfg7", GameError::NotYourPiece);
+    invalid_turn(&mut cte, "Ra7", GameError::NotYourPiece);
     invalid_turn(&mut cte, "Ra6", GameError::CaptureNotSet);
     invalid_turn(&mut cte, "Ra3", GameError::MovingPieceNotFound);
     invalid_turn(&mut cte, "Qa3", GameError::CaptureNotSet);
@@ -281,7 +474,7 @@ fn game_testing_errors2() {
     // Test game over
     let gs = cte.gamestate();
     invalid_turn(&mut cte, "a3", GameError::GameOver(gs));
-    assert_eq!(Gamestate::Victory("Black".to_owned()), cte.gamestate());
+    assert_eq!(Gamestate::Checkmate("Black".to_owned()), cte.gamestate());
 
     // Make sure we can undo turn after game is over
     undo_turns(&mut cte, 4);
@@ -295,7 +488,7 @@ fn game_testing_errors2() {
     play(&mut cte, "Qxd1#");
 
     // Game over again
-    assert_eq!(Gamestate::Victory("Black".to_owned()), cte.gamestate());
+    assert_eq!(Gamestate::Checkmate("Black".to_owned()), cte.gamestate());
 
     cte.display_on_screen(DisplayOption::BoardView(ViewMode::FancyTui));
 }
@@ -394,7 +587,7 @@ fn aron_nimzowitsch_vs_siegbert_tarrasch() {
     play(&mut cte, "Ke6 Re8+");
     play(&mut cte, "Kd7 Bb5#");
 
-    assert_eq!(Gamestate::Victory("Black".to_owned()), cte.gamestate());
+    assert_eq!(Gamestate::Checkmate("Black".to_owned()), cte.gamestate());
 
     cte.display_on_screen(DisplayOption::BoardView(ViewMode::FancyTui));
     cte.display_on_screen(DisplayOption::TurnHistory);
@@ -458,6 +651,28 @@ fn draw_after_only_kings_remain() {
     play(&mut cte, "Kxg5");
 
     assert_eq!(Gamestate::DrawInsufficientMatingMaterial, cte.gamestate());
+    assert_eq!(cte.material_signature(), "KvK");
+}
+
+#[test]
+fn draw_by_insufficient_mating_material_at_setup() {
+    let mut cte = ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K")).unwrap();
+    assert_eq!(cte.gamestate(), Gamestate::DrawInsufficientMatingMaterial);
+    assert!(cte.available_turns().is_empty());
+    assert_eq!(
+        cte.play_turn("Ke2"),
+        Err(GameError::GameOver(
+            Gamestate::DrawInsufficientMatingMaterial
+        ))
+    );
+
+    let cte =
+        ChessTurnEngine::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap();
+    assert_eq!(cte.gamestate(), Gamestate::DrawInsufficientMatingMaterial);
+
+    let cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K a2,w,P")).unwrap();
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
 }
 
 #[test]
@@ -476,6 +691,437 @@ fn draw_by_three_fold_repetition() {
     assert_eq!(cte.gamestate(), Gamestate::DrawThreeFoldRepetition);
 }
 
+#[test]
+fn draws_not_detected() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    cte.detect_draws(false);
+    cte.set_fifty_move_limit(8);
+
+    play(&mut cte, "Nf3 Nf6  Ng1 Ng8  Nf3 Nf6  Ng1 Ng8  Nf3 Nf6");
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+
+    // Game still ends by a checkmate
+    play(&mut cte, "Ng1 Ng8  f3 e5  g4");
+    assert_eq!(
+        cte.play_turn("Qh4#"),
+        Ok(Gamestate::Checkmate("Black".into()))
+    );
+
+    // Insufficient mating material doesn't end the game either
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e2,w,K e8,b,K a1,w,N d3,b,B"))
+            .unwrap();
+    cte.detect_draws(false);
+    play(&mut cte, "Kxd3");
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+
+    cte.detect_draws(true);
+    assert_eq!(
+        cte.play_turn("Kd8"),
+        Ok(Gamestate::DrawInsufficientMatingMaterial)
+    );
+
+    // Position may repeat more times than a byte can count
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    cte.detect_draws(false);
+    for _ in 0..300 {
+        play(&mut cte, "Nf3 Nf6  Ng1 Ng8");
+    }
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+    assert_eq!(cte.repetition_count(), u8::MAX);
+    assert_eq!(cte.halfmoves_since_irreversible(), u8::MAX);
+}
+
+#[test]
+fn lazy_move_generation() {
+    let mut eager = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let mut lazy = ChessTurnEngine::new(Setup::Normal).unwrap();
+    lazy.eager_move_generation(false);
+
+    let turns = |cte: &ChessTurnEngine| -> Vec<String> {
+        cte.available_turns()
+            .iter()
+            .map(|t| t.get_turn().to_string())
+            .collect()
+    };
+
+    for turn in "e4 e5 Bc4 Nc6 Qh5 Nf6".split_whitespace() {
+        assert_eq!(eager.play_turn(turn), lazy.play_turn(turn));
+        assert_eq!(turns(&eager), turns(&lazy));
+    }
+
+    // Game ending is detected without requesting available turns
+    assert_eq!(
+        lazy.play_turn("Qxf7#"),
+        Ok(Gamestate::Checkmate("White".into()))
+    );
+    assert!(lazy.available_turns().is_empty());
+
+    // Undo restores turns of the position, calculated or not
+    undo_turns(&mut lazy, 2);
+    assert!(lazy.play_turn("Nf6").is_ok());
+    undo_turns(&mut lazy, 1);
+    undo_turns(&mut eager, 1);
+    assert_eq!(turns(&eager), turns(&lazy));
+
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("a8,b,K c6,w,K b5,w,Q")).unwrap();
+    cte.eager_move_generation(false);
+    assert_eq!(cte.play_turn("Qb6"), Ok(Gamestate::Stalemate));
+    undo_turns(&mut cte, 1);
+    play(&mut cte, "Qa5+ Kb8 Qb6+ Ka8");
+    assert_eq!(
+        cte.play_turn("Qb7#"),
+        Ok(Gamestate::Checkmate("White".into()))
+    );
+}
+
+#[test]
+fn check_evasions() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.check_evasions().is_empty());
+
+    play(&mut cte, "e4 f5 Qh5+");
+    let evasions: Vec<(CheckEvasionKind, &str)> = cte
+        .check_evasions()
+        .into_iter()
+        .map(|(kind, turn)| (kind, turn.get_turn()))
+        .collect();
+    assert_eq!(evasions, [(CheckEvasionKind::Block, "g6")]);
+
+    // En-passant captures the checking pawn
+    let cte =
+        ChessTurnEngine::from_fen("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1").unwrap();
+    let evasions = cte.check_evasions();
+    assert_eq!(evasions.len(), cte.available_turns().len());
+    for (kind, turn) in evasions {
+        match turn.get_turn() {
+            "exd3" => assert_eq!(kind, CheckEvasionKind::Capture),
+            _ => assert_eq!(kind, CheckEvasionKind::KingMove),
+        }
+    }
+
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K a4,b,R a7,w,R"))
+            .unwrap();
+    play(&mut cte, "Kf1 Ra1+");
+    let kinds: Vec<CheckEvasionKind> = cte
+        .check_evasions()
+        .into_iter()
+        .map(|(kind, _)| kind)
+        .collect();
+    assert_eq!(kinds.len(), 4);
+    assert!(kinds.contains(&CheckEvasionKind::Capture));
+    assert!(kinds.contains(&CheckEvasionKind::KingMove));
+}
+
+#[test]
+fn crazyhouse() {
+    let mut cte =
+        ChessTurnEngine::with_variant(Setup::Normal, GameVariant::Crazyhouse)
+            .unwrap();
+    assert_eq!(cte.variant(), GameVariant::Crazyhouse);
+    assert_eq!(cte.play_turn("N@e5"), Err(GameError::PieceNotInPocket));
+
+    play(&mut cte, "e4 d5 exd5 Qxd5");
+    assert_eq!(cte.pocket("w"), ["Pawn"]);
+    assert_eq!(cte.pocket("b"), ["Pawn"]);
+
+    // Pawn drops on every empty square from the second to the seventh rank
+    let drops: Vec<&AvailableTurn> = cte
+        .available_turns()
+        .iter()
+        .filter(|turn| turn.get_turn().contains('@'))
+        .collect();
+    assert_eq!(drops.len(), 33);
+    assert!(drops.iter().all(|turn| turn.src == turn.dst));
+
+    assert_eq!(cte.play_turn("P@d5"), Err(GameError::InvalidDrop));
+    play(&mut cte, "P@c4");
+    assert!(cte.pocket("w").is_empty());
+    assert_eq!(cte.last_move().as_deref(), Some("P@c4"));
+
+    // Undo puts the dropped piece back into the pocket
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.pocket("w"), ["Pawn"]);
+
+    // Standard game doesn't know about drops
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 exd5");
+    assert!(cte.pocket("w").is_empty());
+    assert_eq!(cte.play_turn("P@e4"), Err(GameError::ParsingTurnFailed));
+}
+
+#[test]
+fn crazyhouse_captured_promoted_piece() {
+    let mut cte = ChessTurnEngine::with_variant(
+        Setup::Custom("e1,w,K h8,b,K b7,w,P a5,b,R"),
+        GameVariant::Crazyhouse,
+    )
+    .unwrap();
+
+    // Promoted queen goes back into the pocket as a pawn
+    play(&mut cte, "b8=Q+ Kh7 Qb5 Rxb5");
+    assert_eq!(cte.pocket("b"), ["Pawn"]);
+    assert_eq!(cte.play_turn("Q@d4"), Err(GameError::PieceNotInPocket));
+
+    undo_turns(&mut cte, 1);
+    assert!(cte.pocket("b").is_empty());
+    assert_eq!(cte.play_turn("Rxb5"), Ok(Gamestate::Ongoing));
+    assert_eq!(cte.pocket("b"), ["Pawn"]);
+}
+
+#[test]
+fn crazyhouse_drop_blocks_check() {
+    // Pawns cannot be dropped on the first rank, so the check is a mate
+    let mut cte = ChessTurnEngine::with_variant(
+        Setup::Custom("a1,w,K a2,w,P b2,w,P c4,w,P h8,b,K h7,b,R d5,b,P"),
+        GameVariant::Crazyhouse,
+    )
+    .unwrap();
+    play(&mut cte, "cxd5 Kg8");
+    assert_eq!(cte.play_turn("P@c1"), Err(GameError::InvalidDrop));
+    assert_eq!(cte.play_turn("@c8"), Err(GameError::InvalidDrop));
+    play(&mut cte, "Kb1 Kh8 Ka1");
+    assert_eq!(
+        cte.play_turn("Rh1#"),
+        Ok(Gamestate::Checkmate("Black".into()))
+    );
+
+    // Captured knight saves the king
+    let mut cte = ChessTurnEngine::with_variant(
+        Setup::Custom("a1,w,K a2,w,P b2,w,P c4,w,P h8,b,K h7,b,R d5,b,N"),
+        GameVariant::Crazyhouse,
+    )
+    .unwrap();
+    play(&mut cte, "cxd5");
+    assert_eq!(cte.play_turn("Rh1+"), Ok(Gamestate::Ongoing));
+
+    let turns: Vec<&str> =
+        cte.available_turns().iter().map(|t| t.get_turn()).collect();
+    assert_eq!(turns, ["N@b1", "N@c1", "N@d1", "N@e1", "N@f1", "N@g1"]);
+    play(&mut cte, "N@e1");
+    assert!(cte.pocket("White").is_empty());
+}
+
+#[test]
+fn toggle_side_to_move() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 e5 f5");
+    assert!(cte.en_passant_available());
+
+    assert_eq!(cte.toggle_side_to_move(), Ok(Gamestate::Ongoing));
+    assert_eq!(cte.last_move(), Some("--".to_string()));
+    assert!(!cte.en_passant_available());
+
+    // Black is on turn again
+    assert!(cte.play_turn("e4").is_err());
+    play(&mut cte, "Nc6");
+
+    // Encoded null turn is replayed as well
+    let replayed =
+        ChessTurnEngine::from_move_bytes(&cte.to_move_bytes().unwrap())
+            .unwrap();
+    assert!(replayed.same_position(&cte));
+    assert_eq!(replayed.last_move(), Some("Nc6".to_string()));
+
+    cte.undo_turn().unwrap();
+    cte.undo_turn().unwrap();
+    assert!(cte.en_passant_available());
+    play(&mut cte, "exf6 Nxf6");
+
+    // Null turn is a regular turn in the notation
+    assert!(cte.play_turn("--").is_ok());
+    play(&mut cte, "Bd7 Qh5+");
+    assert_eq!(cte.toggle_side_to_move(), Err(GameError::KingIsInCheck));
+    assert_eq!(cte.play_turn("--"), Err(GameError::KingIsInCheck));
+}
+
+#[test]
+fn not_your_piece() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    // Black pieces on White's turn
+    invalid_turn(&mut cte, "e5", GameError::NotYourPiece);
+    invalid_turn(&mut cte, "Nf6", GameError::NotYourPiece);
+
+    // Nobody can make the turn
+    invalid_turn(&mut cte, "Ke3", GameError::MovingPieceNotFound);
+    invalid_turn(&mut cte, "Nd5", GameError::MovingPieceNotFound);
+
+    play(&mut cte, "e4");
+    invalid_turn(&mut cte, "Nf3", GameError::NotYourPiece);
+    invalid_turn(&mut cte, "Bc4", GameError::NotYourPiece);
+    play(&mut cte, "e5 Nf3");
+}
+
+#[test]
+fn lazy_available_turns() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 Nc3 Nf6");
+
+    let turns = |iter: &mut dyn Iterator<Item = String>| -> Vec<String> {
+        iter.collect()
+    };
+    let eager = turns(&mut cte.iter_available_turns().map(|t| t.to_string()));
+    let lazy = turns(&mut cte.lazy_available_turns().map(|t| t.to_string()));
+    assert_eq!(eager.len(), cte.available_turns().len());
+    assert_eq!(eager, lazy);
+
+    let capture = cte
+        .lazy_available_turns()
+        .find(|turn| turn.captured.is_some())
+        .unwrap();
+    assert_eq!(capture.get_turn(), "exd5");
+
+    // Drops are generated as well
+    let mut cte =
+        ChessTurnEngine::with_variant(Setup::Normal, GameVariant::Crazyhouse)
+            .unwrap();
+    play(&mut cte, "e4 d5 exd5 Qxd5");
+    assert_eq!(
+        cte.lazy_available_turns().count(),
+        cte.available_turns().len()
+    );
+
+    // Nothing is generated once the game is over
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert_eq!(cte.lazy_available_turns().count(), 0);
+}
+
+#[test]
+fn move_at() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.move_leading_here(), None);
+    assert_eq!(cte.move_at(0), None);
+
+    play(&mut cte, "e4 e5 Nf3");
+    assert_eq!(cte.move_at(0).as_deref(), Some("e4"));
+    assert_eq!(cte.move_at(2).as_deref(), Some("Nf3"));
+    assert_eq!(cte.move_at(3), None);
+    assert_eq!(cte.move_leading_here().as_deref(), Some("Nf3"));
+
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.move_at(2), None);
+    assert_eq!(cte.move_leading_here().as_deref(), Some("e5"));
+}
+
+#[test]
+fn safe_hint() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.safe_hint().is_some());
+
+    // Every pawn move leaves the rook on e1 hanging
+    let cte = ChessTurnEngine::new(Setup::Custom(
+        "g1,w,K f2,w,P g2,w,P h2,w,P e1,w,R \
+         g8,b,K f7,b,P g7,b,P h7,b,P e8,b,R",
+    ))
+    .unwrap();
+    assert_ne!(cte.safe_hint().unwrap().piece, "Pawn");
+
+    // Moving the a-pawn allows the mate on the first rank
+    let mut cte = ChessTurnEngine::new(Setup::Custom(
+        "h1,w,K g2,w,P h2,w,P a3,w,P g8,b,K b8,b,R c5,b,B",
+    ))
+    .unwrap();
+    let mut mated = cte.clone();
+    play(&mut mated, "a4");
+    assert!(mated.find_mate(1).is_some());
+
+    let hint = cte.safe_hint().unwrap().get_turn().to_string();
+    assert_ne!(hint, "a4");
+    play(&mut cte, &hint);
+    assert!(cte.find_mate(1).is_none());
+
+    // Knight is lost anyway, so the first of the turns is picked
+    let cte = ChessTurnEngine::new(Setup::Custom(
+        "a1,w,K e4,w,N h8,b,K d5,b,P d4,b,P b6,b,P e7,b,P e3,b,P h4,b,P \
+         h6,b,P",
+    ))
+    .unwrap();
+    assert_eq!(
+        cte.safe_hint().unwrap().get_turn(),
+        cte.available_turns()[0].get_turn()
+    );
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert!(cte.safe_hint().is_none());
+}
+
+#[test]
+fn play_out() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let mut script = ["f3", "e5", "g4", "Qh4#"].into_iter();
+
+    let gamestate = cte.play_out(10, |turns| {
+        let next = script.next().unwrap();
+        turns.iter().position(|t| t.get_turn() == next).unwrap()
+    });
+    assert_eq!(gamestate, Gamestate::Checkmate("Black".into()));
+    assert_eq!(cte.history().count(), 4);
+
+    // Game over already, nothing gets selected
+    assert_eq!(cte.play_out(10, |_| unreachable!()), gamestate);
+
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K d1,w,R")).unwrap();
+    let gamestate = cte.play_out(usize::MAX, |turns| turns.len() - 1);
+    assert_ne!(gamestate, Gamestate::Ongoing);
+    assert_eq!(cte.gamestate(), gamestate);
+
+    // Game without draws stops after the given number of turns
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    cte.detect_draws(false);
+    let mut script = ["Nf3", "Nf6", "Ng1", "Ng8"].into_iter().cycle();
+    let gamestate = cte.play_out(1000, |turns| {
+        let next = script.next().unwrap();
+        turns.iter().position(|t| t.get_turn() == next).unwrap()
+    });
+    assert_eq!(gamestate, Gamestate::Ongoing);
+    assert_eq!(cte.history().count(), 1000);
+}
+
+#[test]
+fn repetition_count() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.repetition_count(), 0);
+
+    play(&mut cte, "Nf3");
+    assert_eq!(cte.repetition_count(), 1);
+
+    play(&mut cte, "Nf6 Ng1 Ng8 Nf3");
+    assert_eq!(cte.repetition_count(), 2);
+    play(&mut cte, "Nf6 Ng1");
+    assert_eq!(cte.repetition_count(), 2);
+
+    undo_turns(&mut cte, 2);
+    assert_eq!(cte.repetition_count(), 2);
+
+    // Irreversible turn starts counting from scratch
+    play(&mut cte, "e5 Ng1");
+    assert_eq!(cte.repetition_count(), 1);
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "Nf3 Nf6 Ng1 Ng8 Nf3 Nf6 Ng1 Ng8");
+    assert_eq!(cte.repetition_count(), 2);
+    assert_eq!(cte.play_turn("Nf3"), Ok(Gamestate::DrawThreeFoldRepetition));
+    assert_eq!(cte.repetition_count(), 3);
+}
+
+#[test]
+fn repetition_across_undone_pawn_move() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    play(&mut cte, "Nf3 Nf6  Ng1 Ng8  Nf3 Nf6  Ng1 Ng8");
+
+    // Undoing the pawn move brings back the earlier repetitions
+    assert!(cte.play_turn("e4").is_ok());
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.play_turn("Nf3"), Ok(Gamestate::DrawThreeFoldRepetition));
+}
+
 #[test]
 fn draw_by_fifty_move_rule() {
     let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
@@ -544,10 +1190,1997 @@ fn draw_by_fifty_move_rule() {
     play(&mut cte, "Kd6 Qe4  Kc7 Kg3  Kd7 Qh7+");
     play(&mut cte, "Kc6 Qh1+  Kd7 Qe1  Kd6 Qe6+");
     play(&mut cte, "Kc5");
+    assert_eq!(cte.halfmoves_since_irreversible(), 99);
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+    play(&mut cte, "Kg4");
 
     assert_eq!(cte.gamestate(), Gamestate::DrawFiftyMoveRule);
 }
 
+#[test]
+fn custom_fifty_move_limit() {
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K a1,w,R h8,b,R"))
+            .unwrap();
+    cte.set_fifty_move_limit(4);
+    assert_eq!(cte.halfmoves_since_irreversible(), 0);
+    assert!(!cte.is_near_fifty_move_draw(3));
+
+    play(&mut cte, "Kd1 Kd8 Kc1");
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+    assert_eq!(cte.halfmoves_since_irreversible(), 3);
+    assert!(cte.is_near_fifty_move_draw(1));
+    assert!(!cte.is_near_fifty_move_draw(0));
+
+    play(&mut cte, "Kc8");
+    assert_eq!(cte.gamestate(), Gamestate::DrawFiftyMoveRule);
+}
+
+#[test]
+fn halfmoves_since_irreversible() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "Nf3 Nf6 Nc3");
+    assert_eq!(cte.halfmoves_since_irreversible(), 3);
+
+    // Pawn moves and captures reset the counter
+    play(&mut cte, "e5");
+    assert_eq!(cte.halfmoves_since_irreversible(), 0);
+    play(&mut cte, "Nxe5 Bc5");
+    assert_eq!(cte.halfmoves_since_irreversible(), 1);
+
+    undo_turns(&mut cte, 3);
+    assert_eq!(cte.halfmoves_since_irreversible(), 3);
+    assert!(cte.is_near_fifty_move_draw(97));
+    assert!(!cte.is_near_fifty_move_draw(96));
+}
+
+#[test]
+fn find_mate_in_one() {
+    const BACK_RANK: &str = "h1,w,K a1,w,R g8,b,K f7,b,P g7,b,P h7,b,P";
+    let mut cte = ChessTurnEngine::new(Setup::Custom(BACK_RANK)).unwrap();
+    let turns_before = cte.available_turns().len();
+
+    assert_eq!(cte.find_mate(1), Some(vec!["Ra8#".to_string()]));
+
+    // Search must leave the game untouched
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+    assert_eq!(cte.available_turns().len(), turns_before);
+    assert_eq!(cte.undo_turn(), Err(GameError::UndoNotAvailable));
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.find_mate(1), None);
+}
+
+#[test]
+fn evaluate_position() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    // Symmetrical position
+    assert_eq!(cte.evaluate(), 0);
+
+    // White gains mobility by opening lines for the bishop and the queen
+    play(&mut cte, "e4");
+    assert!(cte.evaluate() > 0);
+
+    // Black wins a knight
+    play(&mut cte, "e5 Nf3 Nc6 Ng5 Qxg5");
+    assert!(cte.evaluate() < -200);
+
+    // Fool's mate
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert_eq!(cte.evaluate(), -100_000);
+
+    // Draw
+    let cte = ChessTurnEngine::new(Setup::Custom("a1,w,K h8,b,K")).unwrap();
+    assert_eq!(cte.evaluate(), 0);
+}
+
+#[test]
+fn suggest_move() {
+    const HANGING_QUEEN: &str = "e1,w,K a1,w,R e8,b,K a5,b,Q";
+    let mut cte = ChessTurnEngine::new(Setup::Custom(HANGING_QUEEN)).unwrap();
+    assert_eq!(cte.suggest_move(1), Some("Rxa5".to_string()));
+    assert_eq!(cte.suggest_move(2), Some("Rxa5".to_string()));
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4");
+    assert!(cte.suggest_move(2).is_some());
+    assert_eq!(cte.undo_turn(), Ok(()));
+
+    const BACK_RANK: &str = "h1,w,K a1,w,R g8,b,K f7,b,P g7,b,P h7,b,P";
+    let mut cte = ChessTurnEngine::new(Setup::Custom(BACK_RANK)).unwrap();
+    assert_eq!(cte.suggest_move(2), Some("Ra8#".to_string()));
+    assert_eq!(cte.undo_turn(), Err(GameError::UndoNotAvailable));
+
+    // Game is over
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert_eq!(cte.suggest_move(1), None);
+}
+
+#[test]
+fn perft() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    assert_eq!(cte.perft(0), 1);
+    assert_eq!(cte.perft(1), 20);
+    assert_eq!(cte.perft(2), 400);
+    assert_eq!(cte.perft(3), 8902);
+
+    // Counting must leave the game untouched
+    assert_eq!(cte.available_turns().len(), 20);
+    assert_eq!(cte.undo_turn(), Err(GameError::UndoNotAvailable));
+}
+
+#[test]
+fn perft_divide() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.perft_divide(0).is_empty());
+
+    let divide = cte.perft_divide(3);
+    assert_eq!(divide.len(), 20);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 8902);
+    assert!(divide.contains(&("e4".to_string(), 600)));
+    assert!(divide.contains(&("Nf3".to_string(), 440)));
+
+    // Counting must leave the game untouched
+    assert_eq!(cte.undo_turn(), Err(GameError::UndoNotAvailable));
+
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert!(cte.perft_divide(2).is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn perft_parallel() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    for depth in 0..=3 {
+        assert_eq!(cte.perft_parallel(depth), cte.perft(depth));
+    }
+
+    play(&mut cte, "e4 e5 Nf3");
+    assert_eq!(cte.perft_parallel(3), cte.perft(3));
+}
+
+#[test]
+fn capture_turns() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.capture_turns().is_empty());
+
+    play(&mut cte, "e4 d5 Nc3 c5 e5 f5");
+    let mut captures: Vec<&str> =
+        cte.capture_turns().iter().map(|t| t.get_turn()).collect();
+    captures.sort();
+    assert_eq!(captures, ["Nxd5", "exf6"]);
+
+    let en_passant = cte
+        .capture_turns()
+        .into_iter()
+        .find(|t| t.get_turn() == "exf6")
+        .unwrap();
+    assert_eq!(en_passant.captured.as_deref(), Some("Pawn"));
+}
+
+#[test]
+fn available_turns_grouped() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let grouped = cte.available_turns_grouped();
+    assert_eq!(grouped.keys().collect::<Vec<_>>(), ["Knight", "Pawn"]);
+
+    let knight: Vec<&str> =
+        grouped["Knight"].iter().map(|t| t.get_turn()).collect();
+    assert_eq!(knight, ["Na3", "Nc3", "Nf3", "Nh3"]);
+    assert_eq!(grouped["Pawn"].len(), 16);
+    assert_eq!(
+        grouped.values().map(Vec::len).sum::<usize>(),
+        cte.available_turns().len()
+    );
+
+    play(&mut cte, "e4 e5 Nf3 Nc6 Bc4 Bc5");
+    let grouped = cte.available_turns_grouped();
+    assert_eq!(
+        grouped.keys().collect::<Vec<_>>(),
+        ["Bishop", "King", "Knight", "Pawn", "Queen", "Rook"]
+    );
+    assert!(grouped["King"].iter().any(|t| t.get_turn() == "0-0"));
+}
+
+#[test]
+fn promotion_turns() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.promotion_turns().is_empty());
+
+    let cte =
+        ChessTurnEngine::from_fen("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let promotions = cte.promotion_turns();
+    assert_eq!(promotions.len(), 8);
+    assert!(promotions.iter().all(|t| t.piece == "Pawn"));
+    assert_eq!(
+        promotions.iter().filter(|t| t.captured.is_some()).count(),
+        4
+    );
+    assert!(promotions.iter().any(|t| t.get_turn() == "bxa8=N"));
+}
+
+#[test]
+fn checking_turns() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.checking_turns().is_empty());
+
+    play(&mut cte, "e4 f5");
+    let checks: Vec<&str> =
+        cte.checking_turns().iter().map(|t| t.get_turn()).collect();
+    assert_eq!(checks, ["Qh5+"]);
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4");
+    let checks: Vec<&str> =
+        cte.checking_turns().iter().map(|t| t.get_turn()).collect();
+    assert_eq!(checks, ["Qh4#"]);
+
+    // Promotions giving a check
+    let cte =
+        ChessTurnEngine::new(Setup::Custom("a7,w,P e1,w,K h8,b,K")).unwrap();
+    let mut checks: Vec<&str> =
+        cte.checking_turns().iter().map(|t| t.get_turn()).collect();
+    checks.sort();
+    assert_eq!(checks, ["a8=Q+", "a8=R+"]);
+}
+
+#[test]
+fn discovered_check_moves() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 f5");
+    assert!(cte.discovered_check_moves().is_empty());
+
+    let cte = ChessTurnEngine::new(Setup::Custom(
+        "a1,w,K e1,w,R e4,w,N h1,w,Q e8,b,K",
+    ))
+    .unwrap();
+    let mut checks: Vec<&str> = cte
+        .discovered_check_moves()
+        .iter()
+        .map(|t| t.get_turn())
+        .collect();
+    checks.sort();
+
+    // Every knight move uncovers the rook, two of them are double checks
+    assert_eq!(
+        checks,
+        ["Nc3+", "Nc5+", "Nd2+", "Nd6+", "Nf2+", "Nf6+", "Ng3+", "Ng5+"]
+    );
+    assert!(cte
+        .checking_turns()
+        .iter()
+        .any(|turn| turn.get_turn() == "Qh5+"));
+}
+
+#[test]
+fn promotion_options() {
+    let cte =
+        ChessTurnEngine::new(Setup::Custom("b7,w,P a8,b,R e1,w,K h8,b,K"))
+            .unwrap();
+
+    let mut options = cte.promotion_options("b7");
+    options.sort();
+    for (_, pieces) in options.iter_mut() {
+        pieces.sort();
+    }
+
+    let pieces = ["Bishop", "Knight", "Queen", "Rook"].map(String::from);
+    assert_eq!(
+        options,
+        [
+            (String::from("a8"), pieces.to_vec()),
+            (String::from("b8"), pieces.to_vec())
+        ]
+    );
+
+    // Non-promoting pieces and invalid squares
+    assert!(cte.promotion_options("e1").is_empty());
+    assert!(cte.promotion_options("b6").is_empty());
+    assert!(cte.promotion_options("x9").is_empty());
+}
+
+#[test]
+fn only_move() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(!cte.is_forced());
+    assert!(cte.only_move().is_none());
+
+    // King in check from the rook can only escape to g2
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("h1,w,K h2,w,P a1,b,R a8,b,K"))
+            .unwrap();
+    assert!(cte.is_forced());
+    assert_eq!(cte.only_move().map(|t| t.get_turn()), Some("Kg2"));
+
+    play(&mut cte, "Kg2");
+    assert!(!cte.is_forced());
+    assert!(cte.only_move().is_none());
+
+    // Finished game has no turns at all
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert!(!cte.is_forced());
+    assert!(cte.only_move().is_none());
+}
+
+#[test]
+fn raw_turns_match_available_turns() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 Nc3 Nf6 Qf3 Nxe4");
+
+    let raw_turns = cte.available_turns_raw();
+    let turns = cte.available_turns();
+    assert_eq!(raw_turns.len(), turns.len());
+
+    for (raw, turn) in raw_turns.iter().zip(turns.iter()) {
+        assert_eq!(raw.src.to_string(), turn.src);
+        assert_eq!(raw.dst.to_string(), turn.dst);
+        assert_eq!(raw.piece.to_string(), turn.piece);
+        assert_eq!(raw.captured.map(|p| p.to_string()), turn.captured);
+        assert_eq!(raw.get_turn(), turn.get_turn());
+    }
+
+    // Captures are reported for the raw turns as well
+    assert!(raw_turns
+        .iter()
+        .any(|raw| raw.get_turn() == "Nxe4" && raw.captured.is_some()));
+}
+
+#[test]
+fn piece_at() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    assert_eq!(cte.piece_at("e2"), Some(("Pawn".into(), "White".into())));
+    assert_eq!(cte.piece_at("g8"), Some(("Knight".into(), "Black".into())));
+    assert_eq!(cte.piece_at("e4"), None);
+    assert_eq!(cte.piece_at("x9"), None);
+
+    assert_eq!(cte["d1"], Some((Piece::Queen, Side::White)));
+    assert_eq!(cte["e8"], Some((Piece::King, Side::Black)));
+
+    play(&mut cte, "e4");
+    assert_eq!(cte["e2"], None);
+    assert_eq!(cte["e4"], Some((Piece::Pawn, Side::White)));
+}
+
+#[test]
+fn move_number_prefix() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.play_turn("1. e4").is_ok());
+    assert!(cte.play_turn("1... e5").is_ok());
+    assert!(cte.play_turn("2.Nf3").is_ok());
+    assert_eq!(cte.last_move(), Some("Nf3".to_string()));
+
+    // Strict parsing rejects the move number
+    assert_eq!(
+        cte.play_turn_strict("2... Nc6"),
+        Err(GameError::ParsingTurnFailed)
+    );
+    assert!(cte.play_turn_strict("Nc6").is_ok());
+}
+
+#[test]
+fn available_moves_uci() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let moves = cte.available_moves_uci();
+    assert_eq!(moves.len(), 20);
+    assert!(moves.contains(&"e2e4".to_string()));
+    assert!(moves.contains(&"g1f3".to_string()));
+
+    let cte =
+        ChessTurnEngine::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap();
+    let moves = cte.available_moves_uci();
+    assert_eq!(moves.len(), cte.available_turns().len());
+    for uci in ["e1g1", "e1c1", "b7b8q", "b7b8n", "b7a8r", "b7a8b"] {
+        assert!(moves.contains(&uci.to_string()), "{} missing", uci);
+    }
+}
+
+#[test]
+fn checkmate_and_stalemate_predicates() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4");
+    assert!(!cte.in_checkmate());
+    assert!(!cte.in_stalemate());
+
+    assert!(cte.play_turn("Qh4#").is_ok());
+    assert!(cte.in_checkmate());
+    assert!(!cte.in_stalemate());
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e3 a5 Qh5 Ra6 Qxa5 h5 h4 Rah6 Qxc7 f6");
+    play(&mut cte, "Qxd7+ Kf7 Qxb7 Qd3 Qxb8 Qh7 Qxc8 Kg6 Qe6");
+    assert!(cte.in_stalemate());
+    assert!(!cte.in_checkmate());
+}
+
+#[test]
+fn checkmate_written_as_check() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4");
+    assert_eq!(
+        cte.play_turn("Qh4+"),
+        Ok(Gamestate::Checkmate("Black".to_string()))
+    );
+    assert!(cte.in_checkmate());
+    assert!(!cte.in_stalemate());
+}
+
+#[test]
+fn play_sequence() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.play_sequence("1. e4 e5 2. Nf3"), Ok(Gamestate::Ongoing));
+    assert_eq!(cte.play_sequence(""), Ok(Gamestate::Ongoing));
+
+    // Turns before the illegal one stay played
+    assert_eq!(
+        cte.play_sequence("Nc6 Bc4 Ke7 Qxf7"),
+        Err(("Qxf7".to_string(), GameError::MovingPieceNotFound))
+    );
+    assert_eq!(cte.last_move(), Some("Ke7".to_string()));
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(
+        cte.play_sequence("f3 e5 g4 Qh4#"),
+        Ok(Gamestate::Checkmate("Black".to_string()))
+    );
+}
+
+#[test]
+fn annotated_turns() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.play_turn("f3?").is_ok());
+    assert!(cte.play_turn("e5!").is_ok());
+    assert!(cte.play_turn("g4??").is_ok());
+
+    assert!(cte.play_turn("Qh4#!!").is_ok());
+    assert_eq!(cte.last_move(), Some("Qh4#".to_string()));
+    assert_eq!(cte.result_token(), "0-1");
+
+    // Check flags are still verified
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.play_turn("e4+!?"), Err(GameError::KingIsSafe));
+}
+
+#[test]
+fn result_token() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.result_token(), "*");
+
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    assert_eq!(cte.result_token(), "0-1");
+
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("a1,w,K c2,w,N h8,b,K e3,b,P"))
+            .unwrap();
+    play(&mut cte, "Nxe3");
+    assert_eq!(cte.result_token(), "1/2-1/2");
+}
+
+#[test]
+fn last_move() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.last_move(), None);
+
+    play(&mut cte, "f3 e5");
+    assert_eq!(cte.last_move(), Some("e5".to_string()));
+
+    play(&mut cte, "g4 Qh4#");
+    assert_eq!(cte.gamestate(), Gamestate::Checkmate("Black".to_string()));
+    assert_eq!(cte.last_move(), Some("Qh4#".to_string()));
+
+    undo_turns(&mut cte, 3);
+    assert_eq!(cte.last_move(), Some("f3".to_string()));
+
+    // Drawing turn
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("a1,w,K c2,w,N h8,b,K e3,b,P"))
+            .unwrap();
+    play(&mut cte, "Nxe3");
+    assert_eq!(cte.gamestate(), Gamestate::DrawInsufficientMatingMaterial);
+    assert_eq!(cte.last_move(), Some("Nxe3".to_string()));
+}
+
+#[test]
+fn undo_turn_info() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(matches!(
+        cte.undo_turn_info(),
+        Err(GameError::UndoNotAvailable)
+    ));
+
+    play(&mut cte, "e4 d5 exd5");
+    let record = cte.undo_turn_info().unwrap();
+    assert_eq!(record.turn, "exd5");
+    assert_eq!(record.src.map(|s| s.to_string()), Some("e4".to_string()));
+    assert!(matches!(record.captured, Some(Piece::Pawn)));
+
+    let record = cte.undo_turn_info().unwrap();
+    assert_eq!(record.turn, "d5");
+    assert!(record.captured.is_none());
+    assert_eq!(cte.last_move(), Some("e4".to_string()));
+}
+
+#[test]
+fn control_map() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    let control = cte.control_map();
+    // f3: g2 and e2 pawns, g1 knight
+    assert_eq!(control[5][5], (3, 0));
+    // e1: king is defended by d1 queen
+    assert_eq!(control[7][4], (1, 0));
+    // a6: b7 pawn and b8 knight
+    assert_eq!(control[2][0], (0, 2));
+    assert_eq!(control[4][4], (0, 0));
+
+    play(&mut cte, "e4 d5");
+    let control = cte.control_map();
+    // d5: e4 pawn against d8 queen
+    assert_eq!(control[3][3], (1, 1));
+
+    // Every square matches attackers_of
+    for (row, files) in control.iter().enumerate() {
+        for (file, &(white, black)) in files.iter().enumerate() {
+            let square = format!("{}{}", (b'a' + file as u8) as char, 8 - row);
+            assert_eq!(cte.attackers_of(&square, "w").len() as u8, white);
+            assert_eq!(cte.attackers_of(&square, "b").len() as u8, black);
+        }
+    }
+}
+
+#[test]
+fn undo_turn_details() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(matches!(
+        cte.undo_turn_details(),
+        Err(GameError::UndoNotAvailable)
+    ));
+
+    play(&mut cte, "e4 Nf6 e5 d5 exd6 Nd5 dxc7 Nc6 cxd8=Q+ Kxd8");
+    let info = cte.undo_turn_details().unwrap();
+    assert!(info.from == Some(Square::E8) && info.to == Some(Square::D8));
+    assert!(info.moved_piece == Some(Piece::King));
+    assert!(info.restored_piece == Some((Square::D8, Piece::Queen)));
+
+    // Promoted pawn goes back and the captured queen reappears
+    let info = cte.undo_turn_details().unwrap();
+    assert!(info.from == Some(Square::C7) && info.to == Some(Square::D8));
+    assert!(info.moved_piece == Some(Piece::Pawn));
+    assert!(info.restored_piece == Some((Square::D8, Piece::Queen)));
+
+    undo_turns(&mut cte, 3);
+
+    // Pawn captured en-passant reappears next to the destination square
+    let info = cte.undo_turn_details().unwrap();
+    assert!(info.from == Some(Square::E5) && info.to == Some(Square::D6));
+    assert!(info.restored_piece == Some((Square::D5, Piece::Pawn)));
+
+    let info = cte.undo_turn_details().unwrap();
+    assert!(info.moved_piece == Some(Piece::Pawn));
+    assert!(info.restored_piece.is_none());
+
+    // Castling is the king's move, null turn moves nothing
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3 Nc6 Bc4 Bc5 0-0");
+    assert!(cte.toggle_side_to_move().is_ok());
+
+    let info = cte.undo_turn_details().unwrap();
+    assert!(info.from.is_none() && info.to.is_none());
+    assert!(info.moved_piece.is_none() && info.restored_piece.is_none());
+
+    let info = cte.undo_turn_details().unwrap();
+    assert!(info.from == Some(Square::E1) && info.to == Some(Square::G1));
+    assert!(info.moved_piece == Some(Piece::King));
+}
+
+#[test]
+fn history() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.history().count(), 0);
+
+    play(&mut cte, "e4 d5 exd5 Qxd5 Nc3 Qa5 Nf3 Nf6 Bc4 Bg4");
+    undo_turns(&mut cte, 1);
+    play(&mut cte, "Bf5 0-0");
+
+    let history: Vec<MoveRecord> = cte.history().collect();
+    let turns: Vec<&str> = history.iter().map(|r| r.turn.as_str()).collect();
+    assert_eq!(
+        turns,
+        [
+            "e4", "d5", "exd5", "Qxd5", "Nc3", "Qa5", "Nf3", "Nf6", "Bc4",
+            "Bf5", "0-0"
+        ]
+    );
+
+    let exd5 = &history[2];
+    assert_eq!(exd5.src.map(|s| s.to_string()), Some("e4".to_string()));
+    assert!(matches!(exd5.captured, Some(Piece::Pawn)));
+
+    let qxd5 = &history[3];
+    assert_eq!(qxd5.src.map(|s| s.to_string()), Some("d8".to_string()));
+    assert!(matches!(qxd5.captured, Some(Piece::Pawn)));
+
+    assert!(history[0].captured.is_none());
+
+    // Castling has no single source square
+    assert!(history[10].src.is_none());
+
+    let castles: Vec<bool> = history.iter().map(|r| r.was_castle).collect();
+    let captures: Vec<bool> = history.iter().map(|r| r.was_capture).collect();
+    assert_eq!(castles.iter().filter(|c| **c).count(), 1);
+    assert!(castles[10]);
+    assert_eq!(captures.iter().filter(|c| **c).count(), 2);
+    assert!(captures[2] && captures[3]);
+
+    // Check flags are kept, checkmate counts as a check
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Bc4 Nc6 Bxf7+ Kxf7 Qh5+ g6 Qxe5 Nxe5");
+    let checks: Vec<bool> = cte.history().map(|r| r.was_check).collect();
+    assert_eq!(checks.iter().filter(|c| **c).count(), 2);
+    assert!(checks[4] && checks[6]);
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "f3 e5 g4 Qh4#");
+    let last = cte.history().last().unwrap();
+    assert!(last.was_check && !last.was_capture && !last.was_castle);
+}
+
+#[test]
+fn chess_clock() {
+    use std::time::Duration;
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.time_remaining(Side::White), None);
+
+    cte.set_clock(Clock::new(Duration::from_secs(10), Duration::from_secs(1)));
+    cte.play_turn_timed("e4", Duration::from_secs(3)).unwrap();
+    assert_eq!(
+        cte.time_remaining(Side::White),
+        Some(Duration::from_secs(8))
+    );
+
+    // Undo restores the time
+    undo_turns(&mut cte, 1);
+    assert_eq!(
+        cte.time_remaining(Side::White),
+        Some(Duration::from_secs(10))
+    );
+
+    // Untimed turns are not charged
+    cte.play_turn_timed("e4", Duration::from_secs(3)).unwrap();
+    play(&mut cte, "e5");
+    assert_eq!(
+        cte.time_remaining(Side::Black),
+        Some(Duration::from_secs(10))
+    );
+
+    // Flag fall, the turn is not played
+    assert_eq!(
+        cte.play_turn_timed("Nf3", Duration::from_secs(9)),
+        Ok(Gamestate::Victory("Black".to_string()))
+    );
+    assert_eq!(cte.time_remaining(Side::White), Some(Duration::ZERO));
+    assert!(cte.available_turns().is_empty());
+    assert_eq!(cte.history().count(), 2);
+
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.gamestate(), Gamestate::Ongoing);
+    assert_eq!(
+        cte.time_remaining(Side::White),
+        Some(Duration::from_secs(8))
+    );
+
+    // Flag fall against a bare king is a draw
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K a2,w,P")).unwrap();
+    cte.set_clock(Clock::new(Duration::from_secs(1), Duration::ZERO));
+    assert_eq!(
+        cte.play_turn_timed("a3", Duration::from_secs(2)),
+        Ok(Gamestate::DrawInsufficientMatingMaterial)
+    );
+
+    // So is the flag fall against a king with a single minor piece
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K a2,w,P b8,b,N"))
+            .unwrap();
+    cte.set_clock(Clock::new(Duration::from_secs(1), Duration::ZERO));
+    assert_eq!(
+        cte.play_turn_timed("a3", Duration::from_secs(2)),
+        Ok(Gamestate::DrawInsufficientMatingMaterial)
+    );
+
+    // Mating turn played after the flag fall does not count
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    cte.set_clock(Clock::new(Duration::from_secs(10), Duration::ZERO));
+    play(&mut cte, "f3 e5 g4");
+    assert_eq!(
+        cte.play_turn_timed("Qh4#", Duration::from_secs(11)),
+        Ok(Gamestate::Victory("White".to_string()))
+    );
+    assert_eq!(cte.history().count(), 3);
+}
+
+#[test]
+fn mating_material() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.has_mating_material("w"));
+    assert!(cte.has_mating_material("Black"));
+    assert!(!cte.has_mating_material("x"));
+
+    let cte = ChessTurnEngine::new(Setup::Custom(
+        "e1,w,K c1,w,B e8,b,K b8,b,N g8,b,N",
+    ))
+    .unwrap();
+    assert!(!cte.has_mating_material("white"));
+    assert!(cte.has_mating_material("b"));
+
+    let cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K h2,w,P e8,b,K")).unwrap();
+    assert!(cte.has_mating_material("w"));
+    assert!(!cte.has_mating_material("b"));
+}
+
+#[test]
+fn transposition_table() {
+    let mut table = TranspositionTable::<i32>::new();
+    assert!(table.is_empty());
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let start_key = cte.position_key();
+    assert_eq!(table.insert(&cte, 0), None);
+
+    play(&mut cte, "e4 e5 Nf3 Nc6");
+    assert_ne!(cte.position_key(), start_key);
+    assert!(table.get(&cte).is_none());
+    assert_eq!(table.insert(&cte, 15), None);
+
+    // Same position reached by a different turn order
+    let mut other = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut other, "Nf3 e5 e4 Nc6");
+    assert_eq!(other.position_key(), cte.position_key());
+    assert_eq!(table.get(&other), Some(&15));
+
+    *table.get_mut(&other).unwrap() += 5;
+    assert_eq!(table.insert(&cte, 30), Some(20));
+    assert_eq!(table.len(), 2);
+
+    // Knights going back and forth
+    play(&mut other, "Ng1");
+    assert!(table.get(&other).is_none());
+    play(&mut other, "Nb8 Nf3 Nc6");
+    assert_eq!(table.get(&other), Some(&30));
+
+    assert_eq!(table.remove(&cte), Some(30));
+    assert!(table.remove(&cte).is_none());
+    table.clear();
+    assert!(table.is_empty());
+}
+
+#[test]
+fn goto_ply() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(matches!(cte.redo_turn(), Err(GameError::RedoNotAvailable)));
+
+    play(&mut cte, "e4 e5 Nf3 Nc6 Bc4 Bc5 0-0");
+    let fen = cte.fen();
+
+    assert!(cte.goto_ply(2).is_ok());
+    assert_eq!(cte.history().count(), 2);
+    assert_eq!(cte.last_move().as_deref(), Some("e5"));
+    assert!(cte.available_turns().iter().any(|t| t.get_turn() == "Nf3"));
+
+    // Undone turns are replayed, castling included
+    assert!(cte.goto_ply(7).is_ok());
+    assert_eq!(cte.fen(), fen);
+    assert!(matches!(cte.goto_ply(8), Err(GameError::RedoNotAvailable)));
+    assert_eq!(cte.history().count(), 7);
+
+    assert!(cte.goto_ply(0).is_ok());
+    assert!(cte.redo_turn().is_ok());
+    assert_eq!(cte.last_move().as_deref(), Some("e4"));
+
+    // Playing a different turn discards the undone ones
+    assert!(cte.play_turn("c5").is_ok());
+    assert!(matches!(cte.redo_turn(), Err(GameError::RedoNotAvailable)));
+    assert!(matches!(cte.goto_ply(3), Err(GameError::RedoNotAvailable)));
+
+    // Null turn is replayed as well
+    assert!(cte.toggle_side_to_move().is_ok());
+    assert!(cte.goto_ply(1).is_ok());
+    assert!(cte.goto_ply(3).is_ok());
+    assert_eq!(cte.last_move().as_deref(), Some("--"));
+
+    // Returning to the same position doesn't bring the undone turn back
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "Nf3");
+    assert!(cte.goto_ply(0).is_ok());
+    play(&mut cte, "Nc3 Nc6 Nb1 Nb8");
+    assert!(matches!(cte.redo_turn(), Err(GameError::RedoNotAvailable)));
+    assert_eq!(cte.history().count(), 4);
+}
+
+#[test]
+fn redo_after_search() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3 Nc6");
+    cte.undo_turn().unwrap();
+    cte.undo_turn().unwrap();
+
+    // Searches leave the undone turns intact
+    assert!(cte.suggest_move(1).is_some());
+    assert!(cte.find_mate(1).is_none());
+    let divided = cte.perft_divide(2);
+    assert_eq!(cte.perft(2), divided.iter().map(|(_, nodes)| nodes).sum());
+
+    assert!(cte.redo_turn().is_ok());
+    assert!(cte.redo_turn().is_ok());
+    assert_eq!(cte.last_move().as_deref(), Some("Nc6"));
+}
+
+#[test]
+fn timed_redo() {
+    use std::time::Duration;
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    cte.set_clock(Clock::new(Duration::from_secs(60), Duration::ZERO));
+    cte.play_turn_timed("e4", Duration::from_secs(10)).unwrap();
+    cte.play_turn_timed("e5", Duration::from_secs(7)).unwrap();
+
+    cte.undo_turn().unwrap();
+    assert_eq!(
+        cte.time_remaining(Side::Black),
+        Some(Duration::from_secs(60))
+    );
+
+    // Redone turn keeps its duration and charges the clock again
+    cte.redo_turn().unwrap();
+    assert_eq!(
+        cte.move_durations().collect::<Vec<_>>(),
+        [Some(Duration::from_secs(10)), Some(Duration::from_secs(7))]
+    );
+    assert_eq!(
+        cte.time_remaining(Side::Black),
+        Some(Duration::from_secs(53))
+    );
+    assert_eq!(
+        cte.time_remaining(Side::White),
+        Some(Duration::from_secs(50))
+    );
+}
+
+#[test]
+fn stalemate_in_one() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.stalemate_in_one().is_empty());
+
+    let mut cte =
+        ChessTurnEngine::from_fen("7k/5K2/8/6Q1/8/8/8/8 w - - 0 1").unwrap();
+    let turns: Vec<_> = cte
+        .stalemate_in_one()
+        .iter()
+        .map(|turn| turn.get_turn().to_string())
+        .collect();
+
+    assert!(turns.contains(&String::from("Qg6")));
+    assert!(turns.contains(&String::from("Qf5")));
+    // Checkmate is not a stalemate
+    assert!(!turns.contains(&String::from("Qh6#")));
+    assert!(turns.iter().all(|turn| !turn.ends_with(['+', '#'])));
+
+    for turn in turns {
+        assert_eq!(cte.play_turn(&turn), Ok(Gamestate::Stalemate));
+        assert!(cte.undo_turn().is_ok());
+    }
+}
+
+#[test]
+fn check_status() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.check_status(), CheckStatus::Safe);
+
+    play(&mut cte, "e4 e5 Qh5 Nc6 Bc4 Nf6 Bxf7+");
+    assert_eq!(cte.check_status(), CheckStatus::Check);
+
+    // King can't move, but the knight can be captured
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "Nc3 a6 Nd5 a5 Nf6+");
+    assert!(cte.gamestate() == Gamestate::Ongoing);
+    assert_eq!(cte.check_status(), CheckStatus::Check);
+
+    // Scholar's mate
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Qh5 Nc6 Bc4 Nf6 Qxf7#");
+    assert!(cte.in_checkmate());
+    assert_eq!(cte.check_status(), CheckStatus::Checkmate);
+}
+
+#[test]
+fn from_uci() {
+    let moves = ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "e1g1"];
+    let cte = ChessTurnEngine::from_uci(None, &moves).unwrap();
+    assert_eq!(cte.last_move().as_deref(), Some("0-0"));
+    assert_eq!(
+        cte.fen_placement(),
+        "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1"
+    );
+
+    // Promotion from a FEN start position
+    let cte = ChessTurnEngine::from_uci(
+        Some("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1"),
+        &["b7b8n"],
+    )
+    .unwrap();
+    assert_eq!(cte.last_move().as_deref(), Some("b8=N"));
+
+    assert!(matches!(
+        ChessTurnEngine::from_uci(None, &["e2e4", "e2e4"]),
+        Err(GameError::IllegalUciMove(uci)) if uci == "e2e4"
+    ));
+    assert!(matches!(
+        ChessTurnEngine::from_uci(Some("8/8 w - - 0 1"), &[]),
+        Err(GameError::ParsingFenFailed(_))
+    ));
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.play_uci("e2e4").is_ok());
+    assert!(cte.play_uci("e2e4").is_err());
+    assert_eq!(cte.last_move().as_deref(), Some("e4"));
+}
+
+#[test]
+fn material_signature() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(
+        cte.material_signature(),
+        "KQRRBBNNPPPPPPPPvKQRRBBNNPPPPPPPP"
+    );
+
+    let cte = ChessTurnEngine::new(Setup::Custom(
+        "e1,w,K a1,w,R e4,w,P e8,b,K h8,b,R",
+    ))
+    .unwrap();
+    assert_eq!(cte.material_signature(), "KRPvKR");
+
+    let cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K b8,b,N g7,b,B"))
+            .unwrap();
+    assert_eq!(cte.material_signature(), "KvKBN");
+}
+
+#[test]
+fn theoretical_draw() {
+    fn draw(setup: &'static str) -> bool {
+        ChessTurnEngine::new(Setup::Custom(setup))
+            .unwrap()
+            .is_theoretical_draw()
+    }
+
+    assert!(!ChessTurnEngine::new(Setup::Normal)
+        .unwrap()
+        .is_theoretical_draw());
+
+    assert!(draw("e1,w,K e8,b,K"));
+    assert!(draw("e1,w,K c1,w,B e8,b,K"));
+    assert!(draw("e1,w,K e8,b,K g8,b,N"));
+    assert!(draw("e1,w,K b1,w,N g1,w,N e8,b,K"));
+    assert!(draw("e1,w,K e8,b,K b8,b,N g8,b,N"));
+
+    // Bishops on the same and on the opposite colors
+    assert!(draw("e1,w,K c1,w,B e8,b,K f8,b,B"));
+    assert!(!draw("e1,w,K f1,w,B e8,b,K f8,b,B"));
+
+    assert!(!draw("e1,w,K c1,w,B e8,b,K g8,b,N"));
+    assert!(!draw("e1,w,K b1,w,N g1,w,N e8,b,K g8,b,N"));
+    assert!(!draw("e1,w,K c1,w,B f1,w,B e8,b,K"));
+    assert!(!draw("e1,w,K h2,w,P e8,b,K"));
+    assert!(!draw("e1,w,K e8,b,K a8,b,R"));
+    assert!(!draw("e1,w,K d1,w,Q e8,b,K"));
+}
+
+#[test]
+fn dead_position() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(!cte.is_dead_position());
+
+    let mut cte = ChessTurnEngine::from_fen(
+        "8/8/1k6/p1p1p1p1/P1P1P1P1/8/8/3K4 w - - 0 1",
+    )
+    .unwrap();
+    assert!(cte.is_dead_position());
+    assert!(cte.gamestate() == Gamestate::Ongoing);
+
+    // Kings keep walking, but they never get through
+    play(&mut cte, "Ke2 Kc7 Kf3 Kd6");
+    assert!(cte.is_dead_position());
+
+    // Black g-pawn is free to advance
+    let cte =
+        ChessTurnEngine::from_fen("8/8/1k6/p1p1p1p1/P1P1P3/8/8/3K4 w - - 0 1")
+            .unwrap();
+    assert!(!cte.is_dead_position());
+}
+
+#[test]
+fn file_status() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.file_status(), [FileStatus::Closed; 8]);
+
+    play(&mut cte, "e4 d5 exd5 c6 dxc6 Nxc6");
+    let status = cte.file_status();
+    assert_eq!(status[2], FileStatus::HalfOpenBlack);
+    assert_eq!(status[3], FileStatus::HalfOpenBlack);
+    assert_eq!(status[4], FileStatus::HalfOpenWhite);
+    assert_eq!(status[0], FileStatus::Closed);
+
+    play(&mut cte, "d4 e5 dxe5 Nxe5");
+    let status = cte.file_status();
+    assert_eq!(status[3], FileStatus::Open);
+    assert_eq!(status[4], FileStatus::Open);
+}
+
+#[test]
+fn passed_pawns() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.passed_pawns("w").is_empty());
+    assert!(cte.passed_pawns("b").is_empty());
+    assert!(cte.passed_pawns("x").is_empty());
+
+    // Pawns on the edge files have only one neighboring file
+    let cte = ChessTurnEngine::from_fen("4k3/8/8/1p5P/P7/p7/4P3/4K3 w - - 0 1")
+        .unwrap();
+    assert_eq!(cte.passed_pawns("White"), ["h5", "e2"]);
+    assert_eq!(cte.passed_pawns("b"), ["a3"]);
+
+    // Enemy pawns behind don't stop the pawn
+    let cte =
+        ChessTurnEngine::from_fen("4k3/8/8/3P4/2p5/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(cte.passed_pawns("w"), ["d5"]);
+    assert_eq!(cte.passed_pawns("b"), ["c4"]);
+}
+
+#[test]
+fn pawn_structure() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let structure = cte.pawn_structure("w");
+    assert!(structure.doubled.is_empty() && structure.isolated.is_empty());
+    assert!(cte.pawn_structure("x").doubled.is_empty());
+
+    let cte = ChessTurnEngine::from_fen(
+        "4k3/p1pp1p2/3p4/8/8/P1P2P2/2P2P1P/4K3 w - - 0 1",
+    )
+    .unwrap();
+
+    let names = |squares: Vec<Square>| -> Vec<String> {
+        squares.iter().map(|square| square.to_string()).collect()
+    };
+
+    let white = cte.pawn_structure("White");
+    assert_eq!(names(white.doubled), ["c3", "f3", "c2", "f2"]);
+    assert_eq!(names(white.isolated), ["a3", "c3", "f3", "c2", "f2", "h2"]);
+
+    // Pawn on the edge file has only one neighboring file
+    let black = cte.pawn_structure("b");
+    assert_eq!(names(black.doubled), ["d7", "d6"]);
+    assert_eq!(names(black.isolated), ["a7", "f7"]);
+}
+
+#[test]
+fn move_durations() {
+    use std::time::Duration;
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.move_durations().count(), 0);
+
+    cte.play_turn_timed("e4", Duration::from_secs(2)).unwrap();
+    play(&mut cte, "e5");
+    cte.play_turn_timed("Nf3", Duration::from_millis(1500))
+        .unwrap();
+
+    // Invalid turn is not recorded
+    assert!(cte.play_turn_timed("Nf3", Duration::from_secs(1)).is_err());
+
+    let durations: Vec<Option<Duration>> = cte.move_durations().collect();
+    assert_eq!(
+        durations,
+        [
+            Some(Duration::from_secs(2)),
+            None,
+            Some(Duration::from_millis(1500))
+        ]
+    );
+
+    let checkpoint = cte.checkpoint();
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.move_durations().last(), Some(None));
+
+    cte.restore(&checkpoint);
+    assert_eq!(
+        cte.move_durations().last(),
+        Some(Some(Duration::from_millis(1500)))
+    );
+}
+
+#[test]
+fn square_coords() {
+    assert_eq!(square_to_coords("a1"), Some((0, 0)));
+    assert_eq!(square_to_coords("e4"), Some((4, 3)));
+    assert_eq!(square_to_coords("h8"), Some((7, 7)));
+    assert_eq!(square_to_coords("i1"), None);
+    assert_eq!(square_to_coords("e44"), None);
+
+    assert_eq!(coords_to_square(4, 3), Some("e4".to_string()));
+    assert_eq!(coords_to_square(0, 7), Some("a8".to_string()));
+    assert_eq!(coords_to_square(8, 3), None);
+    assert_eq!(coords_to_square(4, 8), None);
+}
+
+#[test]
+fn squares_between_two_squares() {
+    assert_eq!(squares_between("c1", "f4"), ["d2", "e3"]);
+    assert_eq!(squares_between("e8", "e5"), ["e7", "e6"]);
+    assert_eq!(squares_between("a8", "h1").len(), 6);
+    assert_eq!(squares_between("h1", "e1"), ["g1", "f1"]);
+
+    // Neighbors and squares off the line
+    assert!(squares_between("e4", "f5").is_empty());
+    assert!(squares_between("b1", "c3").is_empty());
+    assert!(squares_between("a1", "i8").is_empty());
+}
+
+#[test]
+fn movetext() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3 Nc6 Bb5");
+    assert_eq!(
+        cte.display(DisplayOption::Movetext),
+        "1. e4 e5 2. Nf3 Nc6 3. Bb5"
+    );
+
+    // Tabbed format stays the default history view
+    assert!(cte.display(DisplayOption::TurnHistory).contains('\t'));
+
+    // Numbering continues from the setup
+    let mut cte = ChessTurnEngine::from_fen(
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    )
+    .unwrap();
+    play(&mut cte, "Bb5 a6 Ba4");
+    assert_eq!(cte.display(DisplayOption::Movetext), "3. Bb5 a6 4. Ba4");
+
+    let mut cte = ChessTurnEngine::from_fen(
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+    )
+    .unwrap();
+    play(&mut cte, "e5 Nf3");
+    assert_eq!(cte.display(DisplayOption::Movetext), "1... e5 2. Nf3");
+}
+
+#[test]
+fn checkpoint_restore() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3");
+
+    let checkpoint = cte.checkpoint();
+    let board = cte.display(DisplayOption::BoardView(ViewMode::SimpleAscii));
+    let history = cte.display(DisplayOption::TurnHistory);
+    let turns = cte.available_turns().clone();
+
+    // Play and undo a few turns after the checkpoint was taken
+    play(&mut cte, "Nc6 Bb5 a6");
+    undo_turns(&mut cte, 5);
+    play(&mut cte, "d5 exd5 Qxd5");
+
+    cte.restore(&checkpoint);
+    assert_eq!(
+        cte.display(DisplayOption::BoardView(ViewMode::SimpleAscii)),
+        board
+    );
+    assert_eq!(cte.display(DisplayOption::TurnHistory), history);
+    assert_eq!(cte.available_turns().len(), turns.len());
+    turns.iter().for_each(|turn| {
+        assert!(available_turns_contain_turn(
+            cte.available_turns(),
+            turn.get_turn()
+        ))
+    });
+
+    // Checkpoint can be reused
+    play(&mut cte, "Nc6 Bb5 a6 Bxc6 dxc6");
+    cte.restore(&checkpoint);
+    assert_eq!(cte.display(DisplayOption::TurnHistory), history);
+    undo_turns(&mut cte, 3);
+    assert_eq!(cte.undo_turn(), Err(GameError::UndoNotAvailable));
+}
+
+#[test]
+fn capture_history_material() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    play(&mut cte, "e4");
+    let captures = cte.display(DisplayOption::CaptureHistory);
+    assert!(captures.contains("Points captured - White: 0, Black: 0\n"));
+    assert!(captures.contains("Material: even\n"));
+
+    play(&mut cte, "d5 exd5 Qxd5 Nc3 Qxa2 Rxa2");
+    let captures = cte.display(DisplayOption::CaptureHistory);
+    assert!(captures.contains("Points captured - White: 10, Black: 2\n"));
+    assert!(captures.contains("Material: White +8\n"));
+
+    undo_turns(&mut cte, 1);
+    let captures = cte.display(DisplayOption::CaptureHistory);
+    assert!(captures.contains("Material: Black +1\n"));
+}
+
+#[test]
+fn capture_history_by_side() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.display(DisplayOption::CaptureHistoryBySide), "");
+
+    play(&mut cte, "e4");
+    assert_eq!(
+        cte.display(DisplayOption::CaptureHistoryBySide),
+        "\nCaptured by White:\nCaptured by Black:\n\n"
+    );
+
+    play(&mut cte, "d5 exd5 Qxd5 Nc3 Qxa2 Rxa2");
+    assert_eq!(
+        cte.display(DisplayOption::CaptureHistoryBySide),
+        "\nCaptured by White: ♟ ♛\nCaptured by Black: ♙ ♙\n\n"
+    );
+}
+
+#[test]
+fn simple_ascii_plain() {
+    let cte = ChessTurnEngine::new(Setup::Custom("a1,w,K h8,b,K")).unwrap();
+
+    let plain =
+        cte.display(DisplayOption::BoardView(ViewMode::SimpleAsciiPlain));
+    assert!(plain.starts_with("8  .  .  .  .  .  .  . bK \n"));
+    assert!(plain.contains("1 wK  .  .  .  .  .  .  . \n"));
+    assert!(!plain.contains(" + ") && !plain.contains(" - "));
+
+    // Default style keeps the checkerboard
+    let checkered =
+        cte.display(DisplayOption::BoardView(ViewMode::SimpleAscii));
+    assert!(checkered.contains(" + ") && checkered.contains(" - "));
+    assert_eq!(plain.len(), checkered.len());
+}
+
+#[test]
+fn simple_ascii_bordered() {
+    let cte = ChessTurnEngine::new(Setup::Custom("a1,w,K h8,b,K")).unwrap();
+
+    let bordered =
+        cte.display(DisplayOption::BoardView(ViewMode::SimpleAsciiBordered));
+    let lines: Vec<&str> = bordered.lines().collect();
+    let files = "   a  b  c  d  e  f  g  h ";
+
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0], files);
+    assert_eq!(lines[1], "8  -  +  -  +  -  +  - bK 8");
+    assert_eq!(lines[8], "1 wK  -  +  -  +  -  +  - 1");
+    assert_eq!(lines[9], files);
+
+    // Squares are the same as in the default style
+    let simple = cte.display(DisplayOption::BoardView(ViewMode::SimpleAscii));
+    for (simple, bordered) in simple.lines().zip(&lines[1..9]) {
+        assert!(bordered.starts_with(simple));
+    }
+}
+
+#[test]
+fn fancy_tui_colors() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let fancy = cte.display(DisplayOption::BoardView(ViewMode::FancyTui));
+
+    // Default scheme matches the plain `FancyTui` output
+    let dark = cte.display(DisplayOption::BoardView(ViewMode::FancyTuiColors(
+        ColorScheme::default(),
+    )));
+    assert_eq!(fancy, dark);
+
+    let light = cte.display(DisplayOption::BoardView(
+        ViewMode::FancyTuiColors(ColorScheme::LIGHT),
+    ));
+    assert_ne!(fancy, light);
+    assert!(light.contains(ColorScheme::LIGHT.light_square));
+
+    let custom = ColorScheme {
+        light_square: "<L>",
+        dark_square: "<D>",
+        grid: "<G>",
+        labels: "<R>",
+    };
+    let board =
+        cte.display(DisplayOption::BoardView(ViewMode::FancyTuiColors(custom)));
+    assert!(board.contains("<G> <R>1 <D> ♖ <L> ♘ "));
+}
+
+#[test]
+fn html_board() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4");
+
+    let html = cte.display(DisplayOption::Html);
+    assert!(html.starts_with("<table"));
+    assert!(html.ends_with("</table>\n"));
+    assert_eq!(html.matches("<tr>").count(), 8);
+    assert_eq!(html.matches("<td ").count(), 64);
+
+    assert!(html.contains("data-square=\"e4\""));
+    assert!(html.contains(
+        "<td data-square=\"h8\" style=\"background-color: #b58863; \
+         width: 48px; height: 48px; text-align: center; font-size: 32px;\">\
+         ♜</td>"
+    ));
+    assert!(
+        html.contains("data-square=\"e2\" style=\"background-color: #f0d9b5;")
+    );
+
+    // Top left corner is a8
+    let a8 = html.find("data-square=\"a8\"").unwrap();
+    let h1 = html.find("data-square=\"h1\"").unwrap();
+    assert!(a8 < h1);
+}
+
+#[test]
+fn validate_game() {
+    assert_eq!(ChessTurnEngine::validate_game(&[]), Ok(Gamestate::Ongoing));
+    assert_eq!(
+        ChessTurnEngine::validate_game(&["e4", "e5", "Nf3", "Nc6"]),
+        Ok(Gamestate::Ongoing)
+    );
+    assert_eq!(
+        ChessTurnEngine::validate_game(&["f3", "e5", "g4", "Qh4#"]),
+        Ok(Gamestate::Checkmate("Black".to_string()))
+    );
+
+    // First failing turn is reported
+    assert_eq!(
+        ChessTurnEngine::validate_game(&["e4", "e5", "Ke3", "Nc6", "Kxe8"]),
+        Err((2, GameError::MovingPieceNotFound))
+    );
+    assert_eq!(
+        ChessTurnEngine::validate_game(&["e4", "e5", "Nf3", "hello"]),
+        Err((3, GameError::ParsingTurnFailed))
+    );
+
+    // No turns can follow the checkmate
+    assert_eq!(
+        ChessTurnEngine::validate_game(&["f3", "e5", "g4", "Qh4#", "a3"]),
+        Err((
+            4,
+            GameError::GameOver(Gamestate::Checkmate("Black".to_string()))
+        ))
+    );
+}
+
+#[test]
+fn validate_fen_records() {
+    assert_eq!(
+        validate_fen(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        ),
+        Ok(())
+    );
+    assert_eq!(validate_fen("8/8/8/4k3/8/8/8/4K3 w - - 0 1"), Ok(()));
+
+    // Custom setups must have two kings, so do FEN records
+    assert!(validate_fen("8/8/8/4k3/8/8/8/8 w - - 0 1").is_err());
+    assert!(validate_fen("8/8/8/4k3/8/8/8/4K3 w - -").is_err());
+    assert!(validate_fen("8/8/8/4k3/8/8/8/4K2 w - - 0 1").is_err());
+    assert!(validate_fen("8/8/8/4k3/8/8/8/4K3 white - - 0 1").is_err());
+    assert!(validate_fen("8/8/8/4k3/8/8/8/4K3 w KQ - 0 1").is_err());
+}
+
+#[test]
+fn turns_and_state_from_fen_records() {
+    let (turns, state) = turns_and_state_from_fen(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    )
+    .unwrap();
+    assert_eq!(turns.len(), 20);
+    assert_eq!(state, Gamestate::Ongoing);
+
+    let (turns, state) = turns_and_state_from_fen(
+        "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3",
+    )
+    .unwrap();
+    assert!(turns.is_empty());
+    assert_eq!(state, Gamestate::Checkmate("Black".to_string()));
+
+    let (turns, state) =
+        turns_and_state_from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(turns.is_empty());
+    assert_eq!(state, Gamestate::Stalemate);
+
+    let (turns, state) =
+        turns_and_state_from_fen("8/8/8/4k3/8/8/8/4K3 w - - 0 1").unwrap();
+    assert!(turns.is_empty());
+    assert_eq!(state, Gamestate::DrawInsufficientMatingMaterial);
+
+    assert!(turns_and_state_from_fen("8/8/8/4k3/8/8/8/8 w - - 0 1").is_err());
+}
+
+#[test]
+fn setup_fen() {
+    const START: &str =
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.setup_fen(), START);
+    play(&mut cte, "e4 e5 Nf3");
+    assert_eq!(cte.setup_fen(), START);
+
+    // Custom setups have no castling rights
+    let cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K e8,b,K a2,w,P")).unwrap();
+    assert_eq!(cte.setup_fen(), "4k3/8/8/8/8/8/P7/4K3 w - - 0 1");
+
+    // Record is kept as it was given, full move number included
+    let fen = "4k3/8/8/8/8/8/P7/R3K3 b Q - 3 42";
+    let mut cte = ChessTurnEngine::from_fen(fen).unwrap();
+    play(&mut cte, "Kd7");
+    assert_eq!(cte.setup_fen(), fen);
+    assert!(ChessTurnEngine::from_fen(&cte.setup_fen()).is_ok());
+}
+
+#[test]
+fn from_fen() {
+    let mut cte = ChessTurnEngine::from_fen(
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+    )
+    .unwrap();
+    assert!(available_turns_contain_turn(cte.available_turns(), "exd6"));
+    play(&mut cte, "exd6");
+    assert_eq!(cte.piece_at("d5"), None);
+
+    // Castling rights are taken from the record
+    let cte =
+        ChessTurnEngine::from_fen("4k3/8/8/8/8/8/8/R3K2R w K - 0 1").unwrap();
+    assert!(available_turns_contain_turn(cte.available_turns(), "0-0"));
+    assert!(!available_turns_contain_turn(
+        cte.available_turns(),
+        "0-0-0"
+    ));
+
+    assert!(matches!(
+        ChessTurnEngine::from_fen("4k3/8/8/8/8/8/8/4K3 w - -"),
+        Err(GameError::ParsingFenFailed(_))
+    ));
+}
+
+#[test]
+fn from_epd() {
+    let (cte, ops) = ChessTurnEngine::from_epd(
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - \
+         bm Qg6; id \"WAC.001\";",
+    )
+    .unwrap();
+
+    assert_eq!(ops.id(), Some("WAC.001"));
+    assert_eq!(ops.best_moves(), ["Qg6"]);
+    assert!(available_turns_contain_turn(cte.available_turns(), "Qg6"));
+
+    assert!(matches!(
+        ChessTurnEngine::from_epd("4k3/8/8/8/8/8/8/4K3 w - - bm Kd2"),
+        Err(GameError::ParsingFenFailed(_))
+    ));
+}
+
+#[test]
+fn mirror_colors() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 e5 f5");
+
+    let mut mirrored = cte.mirror_colors();
+    assert_eq!(
+        mirrored.piece_at("e4"),
+        Some(("Pawn".into(), "Black".into()))
+    );
+    assert_eq!(
+        mirrored.piece_at("d4"),
+        Some(("Pawn".into(), "White".into()))
+    );
+    assert_eq!(
+        mirrored.piece_at("e8"),
+        Some(("King".into(), "Black".into()))
+    );
+    assert_eq!(
+        mirrored.piece_at("e1"),
+        Some(("King".into(), "White".into()))
+    );
+    assert_eq!(mirrored.undo_turn(), Err(GameError::UndoNotAvailable));
+
+    // Black is on turn and can capture en-passant
+    assert_eq!(
+        mirrored.available_turns().len(),
+        cte.available_turns().len()
+    );
+    play(&mut mirrored, "exf3");
+    assert_eq!(mirrored.piece_at("f4"), None);
+
+    // Castling rights are swapped as well
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3 Nf6 Bc4 Bc5");
+    let mut mirrored = cte.mirror_colors();
+    play(&mut mirrored, "0-0 0-0");
+
+    // Mirroring twice gives the original position
+    let board = |cte: &ChessTurnEngine| {
+        cte.display(DisplayOption::BoardView(ViewMode::SimpleAscii))
+    };
+    assert_eq!(board(&cte.mirror_colors().mirror_colors()), board(&cte));
+}
+
+#[test]
+fn rotate_180() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 e5 f5 Nf3 Nc6");
+
+    let rotated = cte.rotate_180().unwrap();
+    assert_eq!(
+        rotated.piece_at("h8"),
+        Some(("Rook".into(), "White".into()))
+    );
+    assert_eq!(
+        rotated.piece_at("d4"),
+        Some(("Pawn".into(), "White".into()))
+    );
+    assert_eq!(
+        rotated.piece_at("d1"),
+        Some(("King".into(), "Black".into()))
+    );
+    assert_eq!(
+        rotated.piece_at("e4"),
+        Some(("Pawn".into(), "Black".into()))
+    );
+    assert_eq!(rotated.piece_at("d5"), None);
+
+    // Neither castling nor en-passant are possible
+    assert!(!available_turns_contain_turn(
+        rotated.available_turns(),
+        "0-0"
+    ));
+    assert!(!rotated
+        .available_turns()
+        .iter()
+        .any(|turn| turn.piece == "Pawn" && turn.captured.is_some()));
+
+    // Rotating twice gives the original placement
+    let board = |cte: &ChessTurnEngine| {
+        cte.display(DisplayOption::BoardView(ViewMode::SimpleAscii))
+    };
+    assert_eq!(board(&rotated.rotate_180().unwrap()), board(&cte));
+
+    // Rotated pawn would attack the king of the player not on turn
+    let cte =
+        ChessTurnEngine::from_fen("8/8/8/8/4P3/3k4/8/4K3 w - - 0 1").unwrap();
+    assert!(cte.rotate_180().is_err());
+
+    // Stalemate by the pawn is gone once the pawn faces the other way
+    let cte =
+        ChessTurnEngine::from_fen("k7/P7/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(cte.in_stalemate());
+    let rotated = cte.rotate_180().unwrap();
+    assert!(rotated.gamestate() == Gamestate::Ongoing);
+    assert_eq!(rotated.fen_placement(), "8/8/8/8/8/6K1/7P/7k");
+}
+
+#[test]
+fn en_passant_availability() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(!cte.en_passant_available());
+    assert_eq!(cte.en_passant_victim(), None);
+
+    play(&mut cte, "e4");
+    assert!(cte.en_passant_available());
+    assert_eq!(cte.en_passant_victim(), Some("e4".to_string()));
+
+    play(&mut cte, "a6 e5 d5");
+    assert_eq!(cte.en_passant_victim(), Some("d5".to_string()));
+
+    // Opportunity lapses after the next turn
+    play(&mut cte, "a3");
+    assert!(!cte.en_passant_available());
+    assert_eq!(cte.en_passant_victim(), None);
+
+    // Undo brings it back
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.en_passant_victim(), Some("d5".to_string()));
+
+    play(&mut cte, "exd6");
+    assert!(!cte.en_passant_available());
+}
+
+#[test]
+fn fen_placement() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(
+        cte.fen_placement(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR"
+    );
+
+    play(&mut cte, "e4 c5 Nf3");
+    assert_eq!(
+        cte.fen_placement(),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R"
+    );
+}
+
+#[test]
+fn same_position() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let mut other = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert!(cte.same_position(&other));
+
+    // Transposition
+    play(&mut cte, "Nf3 Nf6 Nc3");
+    play(&mut other, "Nc3 Nf6 Nf3");
+    assert!(cte.same_position(&other));
+
+    // Different player on turn
+    undo_turns(&mut other, 1);
+    assert!(!cte.same_position(&other));
+
+    // Same pieces, but only one position allows en-passant
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let mut other = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 Nf6 e5 d5");
+    play(&mut other, "e4 d5 e5 Nf6");
+    assert!(!cte.same_position(&other));
+
+    // Same pieces, but castling rights differ
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let mut other = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "Nf3 Nf6 Ng1 Ng8");
+    play(&mut other, "Nf3 Nf6 Rg1 Ng8 Rh1");
+    play(&mut cte, "Nf3");
+    assert!(!cte.same_position(&other));
+}
+
+#[test]
+fn attackers_and_defenders() {
+    let sorted = |mut squares: Vec<String>| {
+        squares.sort();
+        squares
+    };
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 d5 Nc3 Nf6");
+
+    assert_eq!(sorted(cte.attackers_of("d5", "w")), ["c3", "e4"]);
+    assert_eq!(sorted(cte.defenders_of("d5")), ["d8", "f6"]);
+    assert_eq!(sorted(cte.attackers_of("e4", "Black")), ["d5", "f6"]);
+    assert_eq!(sorted(cte.defenders_of("e4")), ["c3"]);
+
+    // Empty squares have no defenders, but can be attacked
+    assert!(cte.defenders_of("e5").is_empty());
+    assert!(cte.attackers_of("e5", "w").is_empty());
+    assert_eq!(sorted(cte.attackers_of("g4", "b")), ["c8", "f6"]);
+
+    // Invalid input
+    assert!(cte.attackers_of("z9", "w").is_empty());
+    assert!(cte.attackers_of("d5", "x").is_empty());
+    assert!(cte.defenders_of("z9").is_empty());
+}
+
+#[test]
+fn defended_squares() {
+    let sorted = |mut squares: Vec<String>| {
+        squares.sort();
+        squares
+    };
+
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(sorted(cte.defended_squares("b1", "w")), ["d2"]);
+    assert_eq!(
+        sorted(cte.defended_squares("d1", "w")),
+        ["c1", "c2", "d2", "e1", "e2"]
+    );
+    assert_eq!(sorted(cte.defended_squares("a1", "w")), ["a2", "b1"]);
+
+    // Empty square, a piece of the other side and invalid input
+    assert!(cte.defended_squares("e4", "w").is_empty());
+    assert!(cte.defended_squares("b8", "w").is_empty());
+    assert!(cte.defended_squares("z9", "w").is_empty());
+    assert!(cte.defended_squares("b1", "x").is_empty());
+    assert_eq!(sorted(cte.defended_squares("b8", "Black")), ["d7"]);
+
+    // Pawn defends diagonally and the queen only the first piece on a line
+    play(&mut cte, "e4 e5 d3 d6");
+    assert_eq!(sorted(cte.defended_squares("d3", "w")), ["e4"]);
+    assert_eq!(
+        sorted(cte.defended_squares("d1", "w")),
+        ["c1", "c2", "d3", "e1"]
+    );
+    assert_eq!(sorted(cte.defended_squares("d6", "b")), ["e5"]);
+}
+
+#[test]
+fn king_square() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.king_square("White"), Some("e1".to_string()));
+    assert_eq!(cte.king_square("b"), Some("e8".to_string()));
+    assert_eq!(cte.king_square("x"), None);
+
+    play(&mut cte, "e4 e5 Ke2 Nf6 Nf3 Bc5 Kd3 0-0");
+    assert_eq!(cte.king_square("w"), Some("d3".to_string()));
+    assert_eq!(cte.king_square("black"), Some("g8".to_string()));
+
+    undo_turns(&mut cte, 1);
+    assert_eq!(cte.king_square("black"), Some("e8".to_string()));
+}
+
+#[test]
+fn piece_count() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    assert_eq!(cte.total_pieces(), 32);
+    assert_eq!(cte.piece_counts(), (16, 16));
+    assert_eq!(cte.piece_count("w", "P"), 8);
+    assert_eq!(cte.piece_count("Black", "Knight"), 2);
+    assert_eq!(cte.piece_count("b", "Q"), 1);
+    assert_eq!(cte.piece_count("white", "king"), 1);
+
+    // Unknown side or piece
+    assert_eq!(cte.piece_count("x", "P"), 0);
+    assert_eq!(cte.piece_count("w", "Dragon"), 0);
+
+    play(&mut cte, "e4 d5 exd5");
+    assert_eq!(cte.piece_counts(), (16, 15));
+
+    play(&mut cte, "Qxd5");
+    assert_eq!(cte.total_pieces(), 30);
+    assert_eq!(cte.piece_counts(), (15, 15));
+    assert_eq!(cte.piece_count("w", "P"), 7);
+    assert_eq!(cte.piece_count("b", "P"), 7);
+}
+
+#[test]
+fn pieces_of() {
+    let mut cte =
+        ChessTurnEngine::new(Setup::Custom("e1,w,K a2,w,P h8,b,K b6,b,N"))
+            .unwrap();
+
+    let pieces = |cte: &ChessTurnEngine, side| {
+        cte.pieces_of(side)
+            .into_iter()
+            .map(|(square, piece)| format!("{} {}", square, piece))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(pieces(&cte, "w"), ["a2 Pawn", "e1 King"]);
+    assert_eq!(pieces(&cte, "Black"), ["h8 King", "b6 Knight"]);
+    assert!(cte.pieces_of("x").is_empty());
+
+    play(&mut cte, "a4 Nxa4");
+    assert_eq!(pieces(&cte, "w"), ["e1 King"]);
+    assert_eq!(pieces(&cte, "b"), ["h8 King", "a4 Knight"]);
+
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.pieces_of("w").len(), 16);
+}
+
+#[test]
+fn board_bytes() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let bytes = cte.board_bytes();
+
+    assert_eq!(bytes[0], 0x42); // a8: Black rook
+    assert_eq!(bytes[12], 0x41); // e7: Black pawn
+    assert_eq!(bytes[60], 0x86); // e1: White king
+    assert_eq!(bytes[59], 0x85); // d1: White queen
+    assert_eq!(bytes[35], 0); // d4: Empty
+
+    play(&mut cte, "e4");
+    let after = cte.board_bytes();
+    assert_eq!(after[52], 0); // e2
+    assert_eq!(after[36], 0x81); // e4: White pawn
+
+    // Every byte agrees with the piece lookup
+    for (idx, byte) in after.iter().enumerate() {
+        assert_eq!(*byte == 0, cte.piece_at_index(idx as u8).is_none());
+    }
+}
+
+#[test]
+fn successors() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    assert_eq!(cte.fen(), cte.setup_fen());
+
+    let successors = cte.successors();
+    assert_eq!(successors.len(), 20);
+    for ((turn, fen), available) in
+        successors.iter().zip(cte.available_turns().iter())
+    {
+        assert_eq!(turn, available.get_turn());
+        assert!(fen.contains(" b KQkq "));
+    }
+
+    let (_, fen) = successors.iter().find(|(turn, _)| turn == "e4").unwrap();
+    play(&mut cte, "e4");
+    assert_eq!(&cte.fen(), fen);
+
+    // Full move number follows the played turns
+    play(&mut cte, "e5 Nf3");
+    assert!(cte.fen().ends_with(" 2"));
+    assert!(cte.successors().iter().all(|(_, fen)| fen.ends_with(" 3")));
+
+    let cte =
+        ChessTurnEngine::from_fen("4k3/8/8/8/8/8/8/R3K3 b Q - 12 40").unwrap();
+    assert!(cte.fen().ends_with(" 40"));
+    assert!(cte.successors().iter().all(|(_, fen)| fen.ends_with(" 41")));
+
+    // Halfmove clock is reset by the pawn move and survives the round trip
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4");
+    assert_eq!(
+        cte.fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+    assert_eq!(
+        ChessTurnEngine::from_fen(&cte.fen()).unwrap().fen(),
+        cte.fen()
+    );
+
+    play(&mut cte, "Nf6");
+    assert_eq!(
+        cte.fen(),
+        "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2"
+    );
+    assert_eq!(
+        ChessTurnEngine::from_fen(&cte.fen()).unwrap().fen(),
+        cte.fen()
+    );
+}
+
+#[test]
+fn captured_piece() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3 Nc6 Bb5 a6");
+
+    for turn in cte.available_turns() {
+        match turn.captured.as_deref() {
+            Some(captured) => {
+                let (piece, side) = turn.captured_piece.unwrap();
+                assert_eq!(piece.to_string(), captured);
+                assert_eq!(side, Side::Black);
+            }
+            None => assert!(turn.captured_piece.is_none()),
+        }
+    }
+
+    let captures = |victim: Piece| {
+        cte.available_turns()
+            .iter()
+            .filter(|turn| turn.captured_piece.map(|(p, _)| p) == Some(victim))
+            .count()
+    };
+    assert_eq!(captures(Piece::Knight), 1);
+    assert_eq!(captures(Piece::Pawn), 2);
+}
+
+#[test]
+fn from_board_bytes() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    play(&mut cte, "e4 e5 Nf3");
+    let bytes = cte.board_bytes();
+
+    let mut restored =
+        ChessTurnEngine::from_board_bytes(bytes, Side::Black).unwrap();
+    assert_eq!(restored.fen_placement(), cte.fen_placement());
+    assert_eq!(restored.board_bytes(), bytes);
+    assert!(restored.history().next().is_none());
+    play(&mut restored, "Nc6");
+
+    // Castling is not available in the restored game
+    let restored = ChessTurnEngine::from_board_bytes(
+        ChessTurnEngine::new(Setup::Normal).unwrap().board_bytes(),
+        Side::White,
+    )
+    .unwrap();
+    assert!(restored.castling_rook_square("w", "short").is_none());
+
+    let err = |bytes: [u8; 64], side: Side| {
+        ChessTurnEngine::from_board_bytes(bytes, side).err()
+    };
+
+    let mut invalid = bytes;
+    invalid[35] = 0x87;
+    assert!(matches!(
+        err(invalid, Side::Black),
+        Some(GameError::InvalidBoardBytes(_))
+    ));
+
+    // King missing and too many kings
+    let mut invalid = bytes;
+    invalid[4] = 0;
+    assert!(matches!(
+        err(invalid, Side::Black),
+        Some(GameError::InvalidBoardBytes(_))
+    ));
+    let mut invalid = bytes;
+    invalid[35] = 0x86;
+    assert!(matches!(
+        err(invalid, Side::Black),
+        Some(GameError::InvalidBoardBytes(_))
+    ));
+
+    // Black king in check while White is on turn
+    let mut invalid = [0; 64];
+    invalid[4] = 0x46;
+    invalid[12] = 0x85;
+    invalid[60] = 0x86;
+    assert!(matches!(
+        err(invalid, Side::White),
+        Some(GameError::InvalidBoardBytes(_))
+    ));
+    assert!(err(invalid, Side::Black).is_none());
+}
+
+#[test]
+fn numeric_squares() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    assert_eq!(square_name(0), Some("a8".to_string()));
+    assert_eq!(square_name(36), Some("e4".to_string()));
+    assert_eq!(square_name(63), Some("h1".to_string()));
+    assert_eq!(square_name(64), None);
+
+    assert_eq!(cte.piece_at_index(4), Some(("King".into(), "Black".into())));
+    assert_eq!(
+        cte.piece_at_index(59),
+        Some(("Queen".into(), "White".into()))
+    );
+    assert_eq!(cte.piece_at_index(36), None);
+    assert_eq!(cte.piece_at_index(100), None);
+
+    // Both conventions agree with each other
+    for idx in 0..64 {
+        let name = square_name(idx).unwrap();
+        assert_eq!(cte.piece_at_index(idx), cte.piece_at(&name));
+    }
+}
+
+#[test]
+fn piece_at_typed() {
+    let mut cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+
+    assert!(matches!(
+        cte.piece_at_typed(Square::G1),
+        Some((Piece::Knight, Side::White))
+    ));
+    assert!(cte.piece_at_typed(Square::F3).is_none());
+
+    play(&mut cte, "Nf3");
+    assert!(cte.piece_at_typed(Square::G1).is_none());
+    assert!(matches!(
+        cte.piece_at_typed(Square::F3),
+        Some((Piece::Knight, Side::White))
+    ));
+
+    // Raw turns speak in the same types
+    assert!(cte.available_turns_raw().iter().all(|turn| matches!(
+        cte.piece_at_typed(turn.src),
+        Some((piece, Side::Black)) if piece == turn.piece
+    )));
+}
+
+#[test]
+#[should_panic(expected = "Invalid square")]
+fn index_invalid_square() {
+    let cte = ChessTurnEngine::new(Setup::Normal).unwrap();
+    let _ = cte["i1"];
+}
+
 /// Play/undo/play so we test 'undo' functionality on every played turn
 fn play(cte: &mut ChessTurnEngine, turns: &str) {
     turns.split_whitespace().for_each(|turn| {